@@ -0,0 +1,62 @@
+//! Generates one test per `inputs/dayN/example*.txt` for `tests/examples.rs`.
+//! Without an `inputs` directory, e.g. in a packaged crate, there are none.
+
+use std::{env, fmt::Write, fs, io, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=inputs");
+
+    let examples = match examples(Path::new("inputs")) {
+        Ok(examples) => examples,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => panic!("cannot list inputs: {}", e),
+    };
+
+    let mut tests = String::new();
+    for (day, example) in examples {
+        writeln!(
+            tests,
+            "#[test]\nfn day{day}_{example}() {{\n    check({day}, \"inputs/day{day}/{example}.txt\");\n}}\n",
+            day = day,
+            example = example
+        )
+        .unwrap();
+    }
+
+    let out = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out).join("examples.rs"), tests).expect("cannot write tests");
+}
+
+/// Day and file stem of every example under `inputs`, sorted.
+fn examples(inputs: &Path) -> io::Result<Vec<(u8, String)>> {
+    let mut examples = Vec::new();
+    for entry in fs::read_dir(inputs)? {
+        let dir = entry?.path();
+        let day = match dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("day"))
+            .and_then(|day| day.parse::<u8>().ok())
+        {
+            Some(day) => day,
+            None => continue,
+        };
+
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let stem = path.file_stem().and_then(|stem| stem.to_str());
+            match stem {
+                Some(stem)
+                    if stem.starts_with("example")
+                        && path.extension().is_some_and(|extension| extension == "txt") =>
+                {
+                    examples.push((day, stem.to_owned()));
+                }
+                _ => {}
+            }
+        }
+    }
+    examples.sort();
+    Ok(examples)
+}
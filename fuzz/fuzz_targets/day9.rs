@@ -0,0 +1,8 @@
+#![no_main]
+
+use advent_of_rust_2020::days::day9;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = day9::read_numbers(data);
+});
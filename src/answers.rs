@@ -0,0 +1,206 @@
+//! Confirmed answers, keyed by a hash of the normalized input they belong to.
+//!
+//! Every day has its own file, `answers/dayN.txt`, with one `<hash> <part> <answer>`
+//! line per known answer. Lines starting with `#` are comments.
+//!
+//! Answers can also travel with an input in a sidecar file, e.g. the puzzle's
+//! answers for `inputs/day14/example.txt` are in `inputs/day14/example.answers`,
+//! one `<part> <answer>` line per part.
+
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+use crate::{inputs, parse, Error, ParseError};
+
+pub const DIR: &str = "answers";
+
+/// Answer of a part that doesn't apply to the input, e.g. part 2 of an
+/// example written for part 1 only. Such parts are not solved when verifying.
+pub const NOT_APPLICABLE: &str = "-";
+
+/// 64-bit FNV-1a hash of the input, as 16 hex digits.
+pub fn hash(data: &[u8]) -> String {
+    let hash = data.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Hash of an input once [`inputs::normalize`]d, the key of its answers. A
+/// copy with `\r\n` line endings or a byte order mark has the same key.
+pub fn hash_input(data: &[u8]) -> String {
+    hash(inputs::normalize(&String::from_utf8_lossy(data)).as_bytes())
+}
+
+/// Path of the sidecar file holding the answers for `input`.
+pub fn sidecar(input: &Path) -> PathBuf {
+    input.with_extension("answers")
+}
+
+/// Splits `<part> <answer>`, the end of a `line` of answers.
+fn part_and_answer<'a>(day: u8, line: &str, rest: &'a str) -> Result<(u8, &'a str), ParseError> {
+    let (part, answer) = rest.split_once(' ').unwrap_or((rest, ""));
+    let part = match part {
+        "1" => 1,
+        "2" => 2,
+        _ => return Err(ParseError::within(day, line, part, "expected part 1 or 2")),
+    };
+    match answer.trim() {
+        "" => Err(ParseError::line(day, line, "missing answer")),
+        answer => Ok((part, answer)),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    /// The answer differs from the recorded one, which is attached.
+    Fail(String),
+    Unknown,
+}
+
+/// Answers of a single day.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    day: u8,
+    entries: BTreeMap<(String, u8), String>,
+}
+
+impl Answers {
+    pub fn new(day: u8) -> Self {
+        Answers {
+            day,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    pub fn path(dir: &Path, day: u8) -> PathBuf {
+        dir.join(format!("day{}.txt", day))
+    }
+
+    /// Reads the answers of `day` stored in `dir`. A missing file means no
+    /// answers are known yet.
+    pub fn load(dir: &Path, day: u8) -> Result<Self, Error> {
+        match fs::File::open(Self::path(dir, day)) {
+            Ok(file) => Self::read(day, BufReader::new(file)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::new(day)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn read<R: BufRead>(day: u8, reader: R) -> Result<Self, Error> {
+        let mut answers = Self::new(day);
+        for line in parse::lines(reader) {
+            let (num, line) = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (hash, rest) = line.split_once(' ').unwrap_or((&line, ""));
+            if hash.len() != 16 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(
+                    ParseError::within(day, &line, hash, "expected a 16 digit hash")
+                        .on_line(num)
+                        .into(),
+                );
+            }
+            let (part, answer) = part_and_answer(day, &line, rest).map_err(|e| e.on_line(num))?;
+            answers.insert(hash, part, answer);
+        }
+        Ok(answers)
+    }
+
+    /// Adds the answers from the sidecar file of `input`, whose contents hash
+    /// to `hash`. Returns whether there is a sidecar.
+    pub fn load_sidecar(&mut self, input: &Path, hash: &str) -> Result<bool, Error> {
+        let file = match fs::File::open(sidecar(input)) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+
+        for line in parse::lines(BufReader::new(file)) {
+            let (num, line) = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (part, answer) =
+                part_and_answer(self.day, &line, &line).map_err(|e| e.on_line(num))?;
+            self.insert(hash, part, answer);
+        }
+        Ok(true)
+    }
+
+    pub fn save(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(Self::path(dir, self.day), self.to_string())
+    }
+
+    pub fn get(&self, hash: &str, part: u8) -> Option<&str> {
+        self.entries
+            .get(&(hash.to_owned(), part))
+            .map(String::as_str)
+    }
+
+    /// Records an answer, returning the one it replaces.
+    pub fn insert(&mut self, hash: &str, part: u8, answer: &str) -> Option<String> {
+        self.entries
+            .insert((hash.to_owned(), part), answer.to_owned())
+    }
+
+    pub fn check(&self, hash: &str, part: u8, answer: &str) -> Verdict {
+        match self.get(hash, part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.to_owned()),
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# input hash, part, answer")?;
+        for ((hash, part), answer) in &self.entries {
+            writeln!(f, "{} {} {}", hash, part, answer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers() {
+        assert_eq!(hash(b""), "cbf29ce484222325");
+        assert_eq!(hash(b"a"), "af63dc4c8601ec8c");
+        assert_eq!(hash_input(b"\xef\xbb\xbf1\r\n2\r\n\r\n"), hash(b"1\n2\n"));
+
+        let text = "# input hash, part, answer\n\
+                    0123456789abcdef 1 514579\n\
+                    0123456789abcdef 2 mxmxvkd,sqjhc,fvjkl\n";
+        let answers = Answers::read(1, text.as_bytes()).unwrap();
+        assert_eq!(answers.to_string(), text);
+        assert_eq!(
+            answers.check("0123456789abcdef", 1, "514579"),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check("0123456789abcdef", 1, "1"),
+            Verdict::Fail("514579".to_owned())
+        );
+        assert_eq!(answers.check("fedcba9876543210", 1, "1"), Verdict::Unknown);
+
+        let error = Answers::read(1, "0123456789abcdef 3 1".as_bytes()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 1, line 1, column 18: expected part 1 or 2 (`3`)"
+        );
+    }
+}
@@ -0,0 +1,500 @@
+//! Cellular automata with a live/dead state per cell.
+//!
+//! An [`Automaton`] is a set of live cells of some [`Topology`], which decides
+//! what the cells and their neighbours are, and a birth/survival [`Rule`].
+//! The cells of a bounded topology are numbered once and kept in vectors,
+//! unbounded ones keep a set of their live cells.
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt,
+    hash::Hash,
+    iter,
+    str::FromStr,
+};
+
+use itertools::Either;
+
+use crate::grid::{self, Grid, Position};
+
+/// The cells of an automaton and how they are connected.
+///
+/// Neighbourhoods have to be symmetric: if `a` is a neighbour of `b`, then `b`
+/// is a neighbour of `a`.
+pub trait Topology {
+    type Cell: Copy + Eq + Hash;
+
+    fn neighbours(&self, cell: Self::Cell) -> Vec<Self::Cell>;
+
+    /// Every cell of a bounded topology. Unbounded topologies only consider
+    /// cells next to live ones, so cells without live neighbours are never
+    /// born there.
+    fn cells(&self) -> Option<Vec<Self::Cell>> {
+        None
+    }
+}
+
+/// Which cells of a [`Square`] topology count as neighbours.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Neighbourhood {
+    /// The four orthogonally adjacent cells.
+    VonNeumann,
+    /// All eight adjacent cells.
+    Moore,
+    /// The nearest cell in each of the eight directions, however far.
+    LineOfSight,
+}
+
+/// A bounded grid where only some of the positions are cells, e.g. the seats
+/// of day 11.
+pub struct Square {
+    neighbours: Grid<Vec<Position>>,
+    cells: Vec<Position>,
+}
+
+impl Square {
+    pub fn new(cells: &Grid<bool>, neighbourhood: Neighbourhood) -> Self {
+        let neighbours = Grid::from_fn(cells.width(), cells.height(), |position| {
+            if !cells[position] {
+                return Vec::new();
+            }
+            match neighbourhood {
+                Neighbourhood::VonNeumann => cells
+                    .neighbours4(position)
+                    .filter(|&neighbour| cells[neighbour])
+                    .collect(),
+                Neighbourhood::Moore => cells
+                    .neighbours8(position)
+                    .filter(|&neighbour| cells[neighbour])
+                    .collect(),
+                Neighbourhood::LineOfSight => grid::ALL
+                    .iter()
+                    .filter_map(|&direction| {
+                        cells.ray(position, direction).find(|&seen| cells[seen])
+                    })
+                    .collect(),
+            }
+        });
+        let cells = cells
+            .iter()
+            .filter(|(_, &cell)| cell)
+            .map(|(position, _)| position)
+            .collect();
+        Square { neighbours, cells }
+    }
+}
+
+impl Topology for Square {
+    type Cell = Position;
+
+    fn neighbours(&self, cell: Position) -> Vec<Position> {
+        self.neighbours.get(cell).cloned().unwrap_or_default()
+    }
+
+    fn cells(&self) -> Option<Vec<Position>> {
+        Some(self.cells.clone())
+    }
+}
+
+/// The unbounded `N`-dimensional integer lattice, where every cell has the
+/// 3^N - 1 cells differing by at most one in each coordinate as neighbours.
+pub struct Lattice<const N: usize> {
+    offsets: Vec<[i32; N]>,
+}
+
+impl<const N: usize> Lattice<N> {
+    pub fn new() -> Self {
+        let mut offsets = vec![[0; N]];
+        for axis in 0..N {
+            offsets = offsets
+                .into_iter()
+                .flat_map(|offset| {
+                    (-1..=1).map(move |delta| {
+                        let mut offset = offset;
+                        offset[axis] = delta;
+                        offset
+                    })
+                })
+                .collect();
+        }
+        offsets.retain(|offset| offset.iter().any(|&delta| delta != 0));
+        Lattice { offsets }
+    }
+}
+
+impl<const N: usize> Default for Lattice<N> {
+    fn default() -> Self {
+        Lattice::new()
+    }
+}
+
+impl<const N: usize> Topology for Lattice<N> {
+    type Cell = [i32; N];
+
+    fn neighbours(&self, cell: [i32; N]) -> Vec<[i32; N]> {
+        self.offsets
+            .iter()
+            .map(|offset| {
+                let mut neighbour = cell;
+                for (coordinate, delta) in neighbour.iter_mut().zip(offset) {
+                    *coordinate += delta;
+                }
+                neighbour
+            })
+            .collect()
+    }
+}
+
+/// The unbounded grid of hexagons with pointy tops, in doubled coordinates:
+/// east and west are two columns away, the diagonal neighbours one column and
+/// one row. See https://www.redblobgames.com/grids/hexagons/#coordinates.
+pub struct Hex;
+
+impl Hex {
+    /// Steps to the east, south-east, south-west, west, north-west and
+    /// north-east neighbour.
+    pub const DIRECTIONS: [(i32, i32); 6] = [(2, 0), (1, 1), (-1, 1), (-2, 0), (-1, -1), (1, -1)];
+}
+
+impl Topology for Hex {
+    type Cell = (i32, i32);
+
+    fn neighbours(&self, (x, y): (i32, i32)) -> Vec<(i32, i32)> {
+        Hex::DIRECTIONS
+            .iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .collect()
+    }
+}
+
+/// Numbers of live neighbours that bring a dead cell to life and keep a live
+/// one alive. Written as in `B3/S23` for Conway's Game of Life, every digit is
+/// a count.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Rule {
+    birth: BTreeSet<usize>,
+    survival: BTreeSet<usize>,
+}
+
+impl Rule {
+    pub fn new(
+        birth: impl IntoIterator<Item = usize>,
+        survival: impl IntoIterator<Item = usize>,
+    ) -> Self {
+        Rule {
+            birth: birth.into_iter().collect(),
+            survival: survival.into_iter().collect(),
+        }
+    }
+
+    pub fn is_alive(&self, alive: bool, live_neighbours: usize) -> bool {
+        if alive {
+            self.survival.contains(&live_neighbours)
+        } else {
+            self.birth.contains(&live_neighbours)
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let counts = |part: Option<&str>, prefix| {
+            let digits = part
+                .and_then(|part| part.strip_prefix(prefix))
+                .ok_or_else(|| format!("expected `B<digits>/S<digits>`, got `{}`", s))?;
+            digits
+                .chars()
+                .map(|ch| {
+                    ch.to_digit(10)
+                        .map(|count| count as usize)
+                        .ok_or_else(|| format!("invalid count `{}` in `{}`", ch, s))
+                })
+                .collect::<Result<BTreeSet<_>, _>>()
+        };
+
+        let mut parts = s.splitn(2, '/');
+        let birth = counts(parts.next(), 'B')?;
+        let survival = counts(parts.next(), 'S')?;
+        Ok(Rule { birth, survival })
+    }
+}
+
+/// Counts of 10 and more have no digit and are left out.
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |counts: &BTreeSet<usize>| -> String {
+            counts
+                .iter()
+                .filter_map(|&count| std::char::from_digit(count as u32, 10))
+                .collect()
+        };
+        write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survival))
+    }
+}
+
+/// The live cells of an [`Automaton`].
+enum State<C> {
+    /// Live cells of an unbounded topology.
+    Sparse(HashSet<C>),
+    /// Every cell of a bounded topology, numbered by its position in `cells`.
+    /// The neighbours are looked up once, and the live neighbour counts are
+    /// kept between steps so that a step allocates nothing.
+    Dense {
+        cells: Vec<C>,
+        index: HashMap<C, usize>,
+        neighbours: Vec<Vec<usize>>,
+        alive: Vec<bool>,
+        counts: Vec<usize>,
+        population: usize,
+    },
+}
+
+pub struct Automaton<T: Topology> {
+    topology: T,
+    rule: Rule,
+    state: State<T::Cell>,
+    generation: usize,
+}
+
+impl<T: Topology> Automaton<T> {
+    /// Live cells that aren't cells of a bounded topology are left out.
+    pub fn new(topology: T, rule: Rule, live: impl IntoIterator<Item = T::Cell>) -> Self {
+        let state = match topology.cells() {
+            Some(cells) => {
+                let index: HashMap<_, _> = cells
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &cell)| (cell, i))
+                    .collect();
+                let neighbours = cells
+                    .iter()
+                    .map(|&cell| {
+                        topology
+                            .neighbours(cell)
+                            .iter()
+                            .filter_map(|neighbour| index.get(neighbour).copied())
+                            .collect()
+                    })
+                    .collect();
+                let mut alive = vec![false; cells.len()];
+                for cell in live {
+                    if let Some(&i) = index.get(&cell) {
+                        alive[i] = true;
+                    }
+                }
+                State::Dense {
+                    population: alive.iter().filter(|&&alive| alive).count(),
+                    counts: vec![0; cells.len()],
+                    cells,
+                    index,
+                    neighbours,
+                    alive,
+                }
+            }
+            None => State::Sparse(live.into_iter().collect()),
+        };
+        Automaton {
+            topology,
+            rule,
+            state,
+            generation: 0,
+        }
+    }
+
+    pub fn topology(&self) -> &T {
+        &self.topology
+    }
+
+    /// The live cells, in no particular order.
+    pub fn live(&self) -> impl Iterator<Item = T::Cell> + '_ {
+        match &self.state {
+            State::Sparse(live) => Either::Left(live.iter().copied()),
+            State::Dense { cells, alive, .. } => Either::Right(
+                cells
+                    .iter()
+                    .zip(alive)
+                    .filter(|(_, &alive)| alive)
+                    .map(|(&cell, _)| cell),
+            ),
+        }
+    }
+
+    pub fn is_alive(&self, cell: T::Cell) -> bool {
+        match &self.state {
+            State::Sparse(live) => live.contains(&cell),
+            State::Dense { index, alive, .. } => index.get(&cell).is_some_and(|&i| alive[i]),
+        }
+    }
+
+    pub fn population(&self) -> usize {
+        match &self.state {
+            State::Sparse(live) => live.len(),
+            State::Dense { population, .. } => *population,
+        }
+    }
+
+    /// Number of generations since the start.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Moves on to the next generation, returns whether any cell changed.
+    pub fn step(&mut self) -> bool {
+        let rule = &self.rule;
+        let changed = match &mut self.state {
+            State::Sparse(live) => {
+                let mut counts: HashMap<T::Cell, usize> = HashMap::new();
+                for &cell in live.iter() {
+                    counts.entry(cell).or_insert(0);
+                    for neighbour in self.topology.neighbours(cell) {
+                        *counts.entry(neighbour).or_insert(0) += 1;
+                    }
+                }
+
+                let next: HashSet<_> = counts
+                    .into_iter()
+                    .filter(|(cell, count)| rule.is_alive(live.contains(cell), *count))
+                    .map(|(cell, _)| cell)
+                    .collect();
+                let changed = next != *live;
+                *live = next;
+                changed
+            }
+            State::Dense {
+                neighbours,
+                alive,
+                counts,
+                population,
+                ..
+            } => {
+                counts.iter_mut().for_each(|count| *count = 0);
+                for (cell_neighbours, _) in neighbours
+                    .iter()
+                    .zip(alive.iter())
+                    .filter(|(_, &alive)| alive)
+                {
+                    for &neighbour in cell_neighbours {
+                        counts[neighbour] += 1;
+                    }
+                }
+
+                let mut changed = false;
+                for (alive, &count) in alive.iter_mut().zip(counts.iter()) {
+                    let next = rule.is_alive(*alive, count);
+                    if next != *alive {
+                        *alive = next;
+                        *population = if next {
+                            *population + 1
+                        } else {
+                            *population - 1
+                        };
+                        changed = true;
+                    }
+                }
+                changed
+            }
+        };
+        self.generation += 1;
+        changed
+    }
+
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Renders the current generation with `render`, then every following one
+    /// until `generations` have passed or, without a limit, until nothing
+    /// changes anymore.
+    pub fn animate<'a, F, U>(
+        mut self,
+        generations: Option<usize>,
+        mut render: F,
+    ) -> impl Iterator<Item = U> + 'a
+    where
+        T: 'a,
+        F: FnMut(&Self) -> U + 'a,
+    {
+        let mut remaining = generations;
+        let mut done = false;
+        iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            let frame = render(&self);
+            match remaining.as_mut() {
+                Some(0) => done = true,
+                Some(remaining) => {
+                    *remaining -= 1;
+                    self.step();
+                }
+                None => done = !self.step(),
+            }
+            Some(frame)
+        })
+    }
+
+    /// Steps until a generation changes nothing, at most `limit` times.
+    /// Returns the number of generations that changed something, `None` if
+    /// the automaton is still changing after `limit` of them.
+    pub fn run_until_stable(&mut self, limit: usize) -> Option<usize> {
+        (0..limit).find(|_| !self.step())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_automaton() {
+        let life: Rule = "B3/S23".parse().unwrap();
+        assert_eq!(life, Rule::new(vec![3], vec![2, 3]));
+        assert_eq!(life.to_string(), "B3/S23");
+        assert!("B3S23".parse::<Rule>().is_err());
+        assert!("B3/Sx".parse::<Rule>().is_err());
+
+        let blinker = vec![[0, -1], [0, 0], [0, 1]];
+        let mut automaton = Automaton::new(Lattice::<2>::new(), life.clone(), blinker.clone());
+        automaton.step();
+        let expected: HashSet<_> = vec![[-1, 0], [0, 0], [1, 0]].into_iter().collect();
+        assert_eq!(automaton.live().collect::<HashSet<_>>(), expected);
+        assert_eq!(automaton.run_until_stable(10), None);
+        assert_eq!(automaton.generation(), 11);
+
+        let block = vec![[0, 0], [0, 1], [1, 0], [1, 1]];
+        let mut automaton = Automaton::new(Lattice::<2>::new(), life, block);
+        assert_eq!(automaton.run_until_stable(10), Some(0));
+        assert_eq!(automaton.population(), 4);
+
+        // Births without live neighbours only happen on bounded topologies.
+        let cells = Grid::new(3, 2, true);
+        let mut automaton = Automaton::new(
+            Square::new(&cells, Neighbourhood::VonNeumann),
+            "B0/S".parse().unwrap(),
+            Vec::new(),
+        );
+        automaton.step();
+        assert_eq!(automaton.population(), 6);
+        assert!(automaton.is_alive((2, 1)));
+        assert!(!automaton.is_alive((3, 1)));
+
+        // A bounded topology steps like an unbounded one away from its edges.
+        let cells = Grid::new(5, 5, true);
+        let mut automaton = Automaton::new(
+            Square::new(&cells, Neighbourhood::Moore),
+            "B3/S23".parse().unwrap(),
+            vec![(2, 1), (2, 2), (2, 3)],
+        );
+        assert!(automaton.step());
+        let expected: HashSet<_> = vec![(1, 2), (2, 2), (3, 2)].into_iter().collect();
+        assert_eq!(automaton.live().collect::<HashSet<_>>(), expected);
+        assert_eq!(automaton.population(), 3);
+        assert_eq!(automaton.run_until_stable(10), None);
+        assert_eq!(Lattice::<4>::new().neighbours([0; 4]).len(), 80);
+        assert_eq!(Hex.neighbours((0, 0)).len(), 6);
+    }
+}
@@ -0,0 +1,110 @@
+//! Timing of parsers and solvers, used by `aoc bench`.
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Durations of repeated runs of the same code.
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    samples: Vec<Duration>,
+}
+
+impl Timings {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        Timings { samples }
+    }
+
+    pub fn runs(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples.first().copied().unwrap_or_default()
+    }
+
+    /// The middle sample, or the lower of the two middle ones.
+    pub fn median(&self) -> Duration {
+        match self.samples.len() {
+            0 => Duration::default(),
+            len => self.samples[(len - 1) / 2],
+        }
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples.last().copied().unwrap_or_default()
+    }
+}
+
+/// Runs `f` up to `runs` times, stopping early once `budget` is spent. `f`
+/// always runs at least once. The last result is returned with the timings so
+/// that the work can't be optimized away and parsed input can be reused.
+pub fn measure<T>(runs: usize, budget: Duration, mut f: impl FnMut() -> T) -> (T, Timings) {
+    let started = Instant::now();
+    let mut samples = Vec::with_capacity(runs);
+    loop {
+        let start = Instant::now();
+        let result = f();
+        samples.push(start.elapsed());
+
+        if samples.len() >= runs || started.elapsed() >= budget {
+            return (result, Timings::from_samples(samples));
+        }
+    }
+}
+
+/// Formats a duration with a unit fitting its magnitude, e.g. `12.3ms`.
+pub struct Elapsed(pub Duration);
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nanos = self.0.as_nanos();
+        let text = if nanos < 1_000 {
+            format!("{}ns", nanos)
+        } else if nanos < 1_000_000 {
+            format!("{:.1}µs", nanos as f64 / 1e3)
+        } else if nanos < 1_000_000_000 {
+            format!("{:.1}ms", nanos as f64 / 1e6)
+        } else {
+            format!("{:.2}s", nanos as f64 / 1e9)
+        };
+        f.pad(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timings() {
+        let timings = Timings::from_samples(
+            [5, 1, 3, 2]
+                .iter()
+                .map(|&ms| Duration::from_millis(ms))
+                .collect(),
+        );
+        assert_eq!(timings.runs(), 4);
+        assert_eq!(timings.min(), Duration::from_millis(1));
+        assert_eq!(timings.median(), Duration::from_millis(2));
+        assert_eq!(timings.max(), Duration::from_millis(5));
+
+        let mut calls = 0;
+        let (result, timings) = measure(3, Duration::from_secs(60), || {
+            calls += 1;
+            calls
+        });
+        assert_eq!((result, timings.runs()), (3, 3));
+
+        let (_, timings) = measure(3, Duration::default(), || ());
+        assert_eq!(timings.runs(), 1);
+
+        assert_eq!(Elapsed(Duration::from_micros(12_345)).to_string(), "12.3ms");
+        assert_eq!(
+            format!("{:>6}", Elapsed(Duration::from_nanos(42))),
+            "  42ns"
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use std::{io::BufRead, path::PathBuf};
+
+use advent_of_rust_2020::{
+    days::{
+        day1::{self, Audit},
+        day2::{self, Policies},
+    },
+    Params,
+};
+use clap::Args;
+use serde::Serialize;
+
+use crate::{default_input, run, select_days};
+
+#[derive(Args)]
+pub struct AuditArgs {
+    /// Day of the input: 1 for an expense report, 2 for a password database
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+    day: u8,
+    /// Puzzle input, `-` for stdin [default: inputs/dayN/1.txt]
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Part whose policy day 2 passwords are checked against [default: 1]
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Overrides a parameter of the day as in `aoc run`, e.g. `--param
+    /// target=2021` on day 1 or `--param part1-policy=count+max-repeats:3` on
+    /// day 2
+    #[arg(long = "param", value_name = "NAME=VALUE")]
+    params: Vec<String>,
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn audit(args: &AuditArgs) -> bool {
+    let day = match select_days(Some(args.day)) {
+        Some(days) => days[0],
+        None => return false,
+    };
+    let params = match Params::new(day.params(), &args.params) {
+        Ok(params) => params,
+        Err(e) => {
+            eprintln!("Day {}: {}", args.day, e);
+            return false;
+        }
+    };
+    match (args.day, args.part) {
+        (1, Some(_)) => eprintln!("Day 1: `--part` only applies to day 2"),
+        (1, None) => return audit_expenses(args, &params),
+        (_, part) => return audit_passwords(args, part.unwrap_or(1), &params),
+    }
+    false
+}
+
+fn audit_expenses(args: &AuditArgs, params: &Params) -> bool {
+    let target = day1::TARGET.get(params);
+    let audit = match read(args, day1::read_entries) {
+        Some(entries) => Audit::new(&entries, target),
+        None => return false,
+    };
+    if args.json {
+        print_json(&audit);
+    } else {
+        print!("{}", audit);
+    }
+    true
+}
+
+fn audit_passwords(args: &AuditArgs, part: u8, params: &Params) -> bool {
+    let policies: Policies = match part {
+        1 => day2::PART1_POLICY.get(params),
+        _ => day2::PART2_POLICY.get(params),
+    };
+    let reports = match read(args, |reader| day2::audit(reader, &policies)) {
+        Some(reports) => reports,
+        None => return false,
+    };
+
+    if args.json {
+        print_json(&reports);
+        return true;
+    }
+    for report in reports
+        .iter()
+        .filter(|report| !report.violations.is_empty())
+    {
+        println!("line {}: {}", report.line, report.entry);
+        for violation in report.violations.iter() {
+            println!("  {}: {}", violation.rule, violation.reason);
+        }
+    }
+    let valid = reports
+        .iter()
+        .filter(|report| report.violations.is_empty())
+        .count();
+    println!(
+        "{} of {} passwords follow {}",
+        valid,
+        reports.len(),
+        policies
+    );
+    true
+}
+
+/// Reads the input of the audited day with `read`, reporting failures on
+/// stderr.
+fn read<T, F, E>(args: &AuditArgs, read: F) -> Option<T>
+where
+    F: FnOnce(Box<dyn BufRead>) -> Result<T, E>,
+    E: std::fmt::Display,
+{
+    let input = args
+        .input
+        .clone()
+        .unwrap_or_else(|| default_input(args.day));
+    let reader = match run::open(&input) {
+        Ok(reader) => reader,
+        Err(e) => {
+            eprintln!("Day {}: cannot open {}: {}", args.day, input.display(), e);
+            return None;
+        }
+    };
+    match read(reader) {
+        Ok(read) => Some(read),
+        Err(e) => {
+            eprintln!("Day {}: invalid input: {}", args.day, e);
+            None
+        }
+    }
+}
+
+fn print_json<T: Serialize>(report: &T) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(report).expect("reports are serializable")
+    );
+}
@@ -0,0 +1,108 @@
+use std::{fs, time::Duration};
+
+use advent_of_rust_2020::{
+    bench::{measure, Elapsed, Timings},
+    registry::DynSolution,
+};
+use clap::Args;
+
+use crate::{catch_solver, collect_with_timeout, default_input, panic_message, select_days};
+
+#[derive(Args)]
+pub struct BenchArgs {
+    /// Day to benchmark [default: all days]
+    #[arg(long)]
+    day: Option<u8>,
+    /// Maximum number of runs of each stage
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    runs: u32,
+    /// Seconds after which a stage stops repeating
+    #[arg(long, default_value_t = 5)]
+    budget: u64,
+    /// Seconds after which a day is abandoned
+    #[arg(long, default_value_t = 60)]
+    timeout: u64,
+}
+
+/// Timings of one stage of a day: parsing or one of the parts.
+type Measurement = (String, Result<Timings, String>);
+
+pub fn bench(args: &BenchArgs) -> bool {
+    let days = match select_days(args.day) {
+        Some(days) => days,
+        None => return false,
+    };
+
+    println!(
+        "{:>3}  {:<6}  {:>4}  {:>9}  {:>9}  {:>9}",
+        "Day", "Stage", "Runs", "Min", "Median", "Max"
+    );
+    let mut ok = true;
+    for day in days {
+        for (stage, timings) in bench_day(day, args) {
+            match timings {
+                Ok(timings) => println!(
+                    "{:>3}  {:<6}  {:>4}  {:>9}  {:>9}  {:>9}",
+                    day.day(),
+                    stage,
+                    timings.runs(),
+                    Elapsed(timings.min()),
+                    Elapsed(timings.median()),
+                    Elapsed(timings.max())
+                ),
+                Err(e) => {
+                    println!("{:>3}  {:<6}  {}", day.day(), stage, e);
+                    ok = false;
+                }
+            }
+        }
+    }
+    ok
+}
+
+/// Benchmarks a day on a separate thread, see [`collect_with_timeout`].
+fn bench_day(day: &'static dyn DynSolution, args: &BenchArgs) -> Vec<Measurement> {
+    let path = default_input(day.day());
+    let data = match fs::read(&path) {
+        Ok(data) => data,
+        Err(e) => {
+            let error = format!("cannot read {}: {}", path.display(), e);
+            return vec![("parse".to_owned(), Err(error))];
+        }
+    };
+
+    let (runs, budget) = (args.runs as usize, Duration::from_secs(args.budget));
+    let timeout = Duration::from_secs(args.timeout);
+    let (mut measurements, timed_out) = collect_with_timeout(timeout, move |sender| {
+        let (input, timings) = measure(runs, budget, || day.parse(&mut data.as_slice()));
+        let input = match input {
+            Ok(input) => input,
+            Err(e) => {
+                let _ = sender.send(("parse".to_owned(), Err(format!("invalid input: {}", e))));
+                return;
+            }
+        };
+        let _ = sender.send(("parse".to_owned(), Ok(timings)));
+
+        for part in 1..=day.parts() {
+            let timings =
+                catch_solver(|| measure(runs, budget, || day.solve(input.as_ref(), part)).1)
+                    .map_err(|e| format!("failed ({})", panic_message(&*e)));
+            let _ = sender.send((format!("part {}", part), timings));
+        }
+    });
+
+    let stage = match measurements.len() {
+        0 => "parse".to_owned(),
+        part => format!("part {}", part),
+    };
+    if timed_out {
+        measurements.push((stage, Err(format!("timed out after {}s", args.timeout))));
+    } else if measurements.len() < 1 + usize::from(day.parts())
+        // Invalid input has already been reported, anything else is a panic.
+        && measurements.last().is_none_or(|(_, timings)| timings.is_ok())
+    {
+        measurements.push((stage, Err("failed".to_owned())));
+    }
+    measurements
+}
@@ -0,0 +1,168 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use advent_of_rust_2020::{
+    render::{self, Image},
+    Params,
+};
+use clap::Args;
+
+use crate::{catch_solver, default_input, panic_message, run::load, select_days};
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Day to export, one of 3, 11, 17, 20 and 24
+    #[arg(long)]
+    day: u8,
+    /// Part whose solution is drawn
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2), default_value_t = 1)]
+    part: u8,
+    /// Puzzle input, `-` for stdin [default: inputs/dayN/1.txt]
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Pixels per side of every cell
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..=64), default_value_t = 4)]
+    scale: u64,
+    /// Write every generation to its own `<name>-NNN.<ext>` file instead of
+    /// only the last one, for `.ppm` and `.png`
+    #[arg(long)]
+    frames: bool,
+    /// Milliseconds between the frames of a `.gif`
+    #[arg(long, default_value_t = 100)]
+    delay: u64,
+    /// Overrides a parameter of the day as in `aoc run`, e.g. `--param
+    /// part2-slopes=1/1,-1/1` on day 3
+    #[arg(long = "param", value_name = "NAME=VALUE")]
+    params: Vec<String>,
+    /// File to write, a `.ppm`, `.png` or an animated `.gif`
+    output: PathBuf,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Ppm,
+    Png,
+    Gif,
+}
+
+pub fn export(args: &ExportArgs) -> bool {
+    let format = match args.output.extension().and_then(|ext| ext.to_str()) {
+        Some("ppm") => Format::Ppm,
+        Some("png") => Format::Png,
+        Some("gif") => Format::Gif,
+        _ => {
+            eprintln!(
+                "Cannot tell the format of {}, expected .ppm, .png or .gif",
+                args.output.display()
+            );
+            return false;
+        }
+    };
+    let day = match select_days(Some(args.day)) {
+        Some(days) => days[0],
+        None => return false,
+    };
+    if args.part > day.parts() {
+        eprintln!("Day {} has no part {}", args.day, args.part);
+        return false;
+    }
+    let params = match Params::new(day.params(), &args.params) {
+        Ok(params) => params,
+        Err(e) => {
+            eprintln!("Day {}: {}", args.day, e);
+            return false;
+        }
+    };
+    let input = args
+        .input
+        .clone()
+        .unwrap_or_else(|| default_input(args.day));
+    let parsed = match load(day, &input) {
+        Some(parsed) => parsed,
+        None => return false,
+    };
+
+    let frames = catch_solver(|| {
+        render::animation(args.day, parsed.as_ref(), args.part, &params)
+            .map(Iterator::collect::<Vec<_>>)
+    });
+    let frames = match frames {
+        Ok(Some(frames)) => frames,
+        Ok(None) => {
+            eprintln!("Day {} has no visualization", args.day);
+            return false;
+        }
+        Err(e) => {
+            eprintln!("Day {} failed: {}", args.day, panic_message(&*e));
+            return false;
+        }
+    };
+
+    let scale = args.scale as usize;
+    let images: Vec<Image> = frames
+        .iter()
+        .map(|frame| Image::new(frame, scale))
+        .collect();
+    let written = match format {
+        Format::Gif => write(&args.output, |writer| {
+            render::write_gif(&images, Duration::from_millis(args.delay), writer)
+        }),
+        _ if args.frames => images
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, image)| write_image(&numbered(&args.output, i), image, format)),
+        _ => write_image(
+            &args.output,
+            images.last().expect("an initial frame"),
+            format,
+        ),
+    };
+    match written {
+        Ok(()) => {
+            let count = if format == Format::Gif || args.frames {
+                images.len()
+            } else {
+                1
+            };
+            println!(
+                "Day {}, part {}: wrote {} frame(s) to {}",
+                args.day,
+                args.part,
+                count,
+                args.output.display()
+            );
+            true
+        }
+        Err(e) => {
+            eprintln!("Cannot write {}: {}", args.output.display(), e);
+            false
+        }
+    }
+}
+
+/// `path` with the frame number appended to the file name, e.g. `seats-007.png`.
+fn numbered(path: &Path, frame: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = path.extension().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{}-{:03}.{}", stem, frame, ext))
+}
+
+fn write_image(path: &Path, image: &Image, format: Format) -> io::Result<()> {
+    write(path, |writer| match format {
+        Format::Ppm => image.write_ppm(writer),
+        _ => image.write_png(writer),
+    })
+}
+
+fn write(
+    path: &Path,
+    contents: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    contents(&mut writer)?;
+    writer.flush()
+}
@@ -0,0 +1,64 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use advent_of_rust_2020::{answers, registry};
+use clap::Args;
+
+#[derive(Args)]
+pub struct GenArgs {
+    /// Day to generate an input for
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Seed of the input, the same seed gives the same input
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+    /// Size of the input, e.g. entries on day 1 [default: about the size of a puzzle input]
+    #[arg(long)]
+    size: Option<usize>,
+    /// File to write the input to, with the known answers next to it in a
+    /// `.answers` file [default: stdout]
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+pub fn gen(args: &GenArgs) -> bool {
+    let generator = match registry::find(args.day).and_then(|day| day.generator()) {
+        Some(generator) => generator,
+        None => {
+            eprintln!("Day {} has no generator", args.day);
+            return false;
+        }
+    };
+    let generated = match generator.generate(args.seed, args.size) {
+        Ok(generated) => generated,
+        Err(e) => {
+            eprintln!("Day {}: {}", args.day, e);
+            return false;
+        }
+    };
+
+    let written = match &args.output {
+        Some(path) => fs::write(path, &generated.input).and_then(|()| {
+            if generated.answers.iter().any(Option::is_some) {
+                fs::write(answers::sidecar(path), generated.sidecar())
+            } else {
+                Ok(())
+            }
+        }),
+        None => io::stdout().write_all(generated.input.as_bytes()),
+    };
+    if let Err(e) = written {
+        eprintln!("Cannot write the input: {}", e);
+        return false;
+    }
+
+    for (part, answer) in (1..).zip(&generated.answers) {
+        if let Some(answer) = answer {
+            eprintln!("Day {}, part {}: {}", args.day, part, answer);
+        }
+    }
+    true
+}
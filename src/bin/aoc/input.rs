@@ -0,0 +1,128 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use advent_of_rust_2020::{answers, inputs, Error};
+use clap::{Args, Subcommand};
+
+use crate::select_days;
+
+#[derive(Subcommand)]
+pub enum InputCommand {
+    /// Validates and stores an input as `inputs/dayN/<name>.txt`
+    Add(InputAddArgs),
+    /// Lists the inputs of a single day or all of them with their hashes
+    List {
+        /// Day to list [default: all days]
+        #[arg(long)]
+        day: Option<u8>,
+    },
+}
+
+#[derive(Args)]
+pub struct InputAddArgs {
+    /// Day the input belongs to
+    #[arg(long)]
+    day: u8,
+    /// Name to store the input under, e.g. the owner of the input
+    #[arg(long)]
+    name: String,
+    /// Replace an input stored under the same name
+    #[arg(long)]
+    force: bool,
+    /// File with the input, `-` for stdin
+    path: PathBuf,
+}
+
+pub fn input(command: &InputCommand) -> bool {
+    match command {
+        InputCommand::Add(args) => add(args),
+        InputCommand::List { day } => list(*day),
+    }
+}
+
+fn add(args: &InputAddArgs) -> bool {
+    let day = match select_days(Some(args.day)) {
+        Some(days) => days[0],
+        None => return false,
+    };
+    let data = if args.path == Path::new("-") {
+        let mut data = Vec::new();
+        io::stdin().read_to_end(&mut data).map(|_| data)
+    } else {
+        fs::read(&args.path)
+    };
+    let data = match data {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!(
+                "Day {}: cannot read {}: {}",
+                args.day,
+                args.path.display(),
+                e
+            );
+            return false;
+        }
+    };
+
+    match inputs::add(Path::new(inputs::DIR), day, &args.name, &data, args.force) {
+        Ok(path) => {
+            let hash = answers::hash_input(&fs::read(&path).unwrap_or_default());
+            println!("Stored {} ({})", path.display(), hash);
+            true
+        }
+        Err(Error::Io(e)) if e.kind() == io::ErrorKind::AlreadyExists => {
+            eprintln!(
+                "Day {}: input {} exists already, use --force to replace it",
+                args.day, args.name
+            );
+            false
+        }
+        Err(Error::Io(e)) if e.kind() == io::ErrorKind::InvalidInput => {
+            eprintln!("Day {}: {}", args.day, e);
+            false
+        }
+        Err(e) => {
+            eprintln!("Day {}: cannot add input: {}", args.day, e);
+            false
+        }
+    }
+}
+
+fn list(day: Option<u8>) -> bool {
+    let days = match select_days(day) {
+        Some(days) => days,
+        None => return false,
+    };
+
+    let mut ok = true;
+    for day in days {
+        println!("Day {}", day.day());
+        let day_inputs = match inputs::list(Path::new(inputs::DIR), day.day()) {
+            Ok(day_inputs) => day_inputs,
+            Err(e) => {
+                eprintln!("Day {}: cannot list inputs: {}", day.day(), e);
+                ok = false;
+                continue;
+            }
+        };
+        for path in day_inputs {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            match fs::read(&path) {
+                Ok(data) => println!(
+                    "  {:<16} {}  {} lines",
+                    name,
+                    answers::hash_input(&data),
+                    data.iter().filter(|&&byte| byte == b'\n').count()
+                ),
+                Err(e) => {
+                    eprintln!("Day {}, {}: cannot read: {}", day.day(), name, e);
+                    ok = false;
+                }
+            }
+        }
+    }
+    ok
+}
@@ -0,0 +1,174 @@
+use std::{
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process,
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use advent_of_rust_2020::{
+    inputs,
+    registry::{self, DynSolution},
+    scaffold,
+};
+use clap::{Args, Parser, Subcommand};
+
+mod audit;
+mod bench;
+mod export;
+mod gen;
+mod input;
+mod run;
+mod verify;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2020 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solves a single day or all of them
+    Run(run::RunArgs),
+    /// Times parsing and both parts of a single day or all of them
+    Bench(bench::BenchArgs),
+    /// Draws the solution of a day as an image or an animated GIF
+    Export(export::ExportArgs),
+    /// Lists the pairs and triples of day 1 entries summing to the target, or the rules day 2 passwords break
+    Audit(audit::AuditArgs),
+    /// Generates a random input of a day, with the answers known from building it
+    Gen(gen::GenArgs),
+    /// Checks the answers for every input in `inputs/dayN` against `answers/dayN.txt`
+    Verify(verify::VerifyArgs),
+    /// Stores confirmed answers for an input in `answers/dayN.txt`
+    Record(verify::RecordArgs),
+    /// Creates and registers the module and input directory of a new day
+    New(NewArgs),
+    /// Manages the puzzle inputs in `inputs/dayN`
+    #[command(subcommand)]
+    Input(input::InputCommand),
+    /// Lists the registered days and the parameters of their puzzles
+    List,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    /// Day to create
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Year of the puzzle, for the link in the module
+    #[arg(long, default_value_t = 2020)]
+    year: u16,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !SOLVING.with(Cell::get) {
+            default_hook(info);
+        }
+    }));
+    let ok = match cli.command {
+        Command::Run(args) => run::run(&args),
+        Command::Bench(args) => bench::bench(&args),
+        Command::Export(args) => export::export(&args),
+        Command::Audit(args) => audit::audit(&args),
+        Command::Gen(args) => gen::gen(&args),
+        Command::Verify(args) => verify::verify(&args),
+        Command::Record(args) => verify::record(&args),
+        Command::New(args) => match scaffold::scaffold(Path::new("."), args.day, args.year) {
+            Ok(created) => {
+                for path in created {
+                    println!("Created {}", path.display());
+                }
+                true
+            }
+            Err(e) => {
+                eprintln!("Cannot create day {}: {}", args.day, e);
+                false
+            }
+        },
+        Command::Input(command) => input::input(&command),
+        Command::List => {
+            for day in registry::all() {
+                println!("Day {} ({} parts)", day.day(), day.parts());
+                for param in day.params() {
+                    println!("  {}={}  {}", param.name, param.default, param.help);
+                }
+            }
+            true
+        }
+    };
+
+    if !ok {
+        process::exit(1);
+    }
+}
+
+/// Runs `work` on a separate thread and collects what it sends until it is
+/// done or `timeout` passes, which is signalled by the returned flag. A
+/// solver exceeding the timeout is left behind, so that the remaining days
+/// don't have to wait for it.
+fn collect_with_timeout<T, F>(timeout: Duration, work: F) -> (Vec<T>, bool)
+where
+    T: Send + 'static,
+    F: FnOnce(&Sender<T>) + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || work(&sender));
+
+    let deadline = Instant::now() + timeout;
+    let mut messages = Vec::new();
+    loop {
+        match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(message) => messages.push(message),
+            Err(RecvTimeoutError::Timeout) => return (messages, true),
+            Err(RecvTimeoutError::Disconnected) => return (messages, false),
+        }
+    }
+}
+
+/// The given day or all of them, `None` if the day is not registered.
+fn select_days(day: Option<u8>) -> Option<Vec<&'static dyn DynSolution>> {
+    match day {
+        Some(day) => match registry::find(day) {
+            Some(solution) => Some(vec![solution]),
+            None => {
+                eprintln!("Day {} is not registered", day);
+                None
+            }
+        },
+        None => Some(registry::all().to_vec()),
+    }
+}
+
+fn default_input(day: u8) -> PathBuf {
+    inputs::day_dir(Path::new(inputs::DIR), day).join("1.txt")
+}
+
+thread_local! {
+    /// Whether the thread is in [`catch_solver`], whose panics are reported
+    /// next to the answers instead of by the default panic hook.
+    static SOLVING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs a solver, catching its panic to be reported as a failed part.
+fn catch_solver<T>(solve: impl FnOnce() -> T) -> thread::Result<T> {
+    let solving = SOLVING.with(|solving| solving.replace(true));
+    let solved = panic::catch_unwind(AssertUnwindSafe(solve));
+    SOLVING.with(|cell| cell.set(solving));
+    solved
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("panicked")
+}
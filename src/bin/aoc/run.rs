@@ -0,0 +1,568 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    convert::TryFrom,
+    env,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use advent_of_rust_2020::{bench::Elapsed, inputs, registry::DynSolution, render, Params};
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+
+use crate::{catch_solver, default_input, panic_message, select_days};
+
+#[derive(Args)]
+pub struct RunArgs {
+    /// Day to solve
+    #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+    /// Solve only this part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Puzzle input, `-` for stdin [default: inputs/dayN/1.txt]
+    #[arg(long, conflicts_with = "all")]
+    input: Option<PathBuf>,
+    /// Solve every registered day in parallel and print a summary
+    #[arg(long)]
+    all: bool,
+    /// Solve every input in `inputs/dayN`, not just `1.txt`
+    #[arg(long, requires = "all")]
+    all_inputs: bool,
+    /// Number of inputs solved at the same time [default: number of CPUs].
+    /// Inputs that timed out keep running on top of these
+    #[arg(long, requires = "all", value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: Option<u32>,
+    /// Seconds after which an input is abandoned
+    #[arg(long, requires = "all", default_value_t = 60)]
+    timeout: u64,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Animate the solution in the terminal before printing the answer, for
+    /// days 3, 11, 17, 20 and 24. Colours are left out if `NO_COLOR` is set
+    #[arg(long, conflicts_with_all = ["all", "format"])]
+    visualize: bool,
+    /// Milliseconds between the frames of `--visualize`
+    #[arg(long, requires = "visualize", default_value_t = 100)]
+    delay: u64,
+    /// Solve both parts in a single pass over the input without keeping all
+    /// of it, for days 1, 2, 5, 9, 12, 18 and 24
+    #[arg(long, conflicts_with_all = ["all", "visualize"])]
+    stream: bool,
+    /// Overrides a value the puzzle fixes, e.g. `--param preamble=5` on day
+    /// 9. `aoc list` shows the parameters of every day
+    #[arg(long = "param", value_name = "NAME=VALUE", conflicts_with = "all")]
+    params: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// One `Day N, part P: answer` line per part, a table with `--all`
+    Text,
+    /// A JSON array with one record per part, including timings
+    Json,
+}
+
+/// Outcome of solving one part of a day. Failures to read or parse the input
+/// are reported once for the whole day, without a part.
+#[derive(Serialize)]
+struct Report {
+    day: u8,
+    part: Option<u8>,
+    answer: Option<String>,
+    answer_type: Option<&'static str>,
+    parse_ns: Option<u64>,
+    solve_ns: Option<u64>,
+    input: PathBuf,
+    error: Option<String>,
+    timed_out: bool,
+}
+
+impl Report {
+    fn new(day: u8, input: &Path) -> Self {
+        Report {
+            day,
+            part: None,
+            answer: None,
+            answer_type: None,
+            parse_ns: None,
+            solve_ns: None,
+            input: input.to_owned(),
+            error: None,
+            timed_out: false,
+        }
+    }
+}
+
+pub fn run(args: &RunArgs) -> bool {
+    if args.all {
+        return run_all(args);
+    }
+
+    let days = match select_days(args.day) {
+        Some(days) => days,
+        None => return false,
+    };
+    if let Some(part) = args.part.filter(|&part| part > days[0].parts()) {
+        eprintln!("Day {} has no part {}, only one", days[0].day(), part);
+        return false;
+    }
+
+    let params = match Params::new(days[0].params(), &args.params) {
+        Ok(params) => params,
+        Err(e) => {
+            eprintln!("Day {}: {}", days[0].day(), e);
+            return false;
+        }
+    };
+
+    if args.visualize {
+        let input = args
+            .input
+            .clone()
+            .unwrap_or_else(|| default_input(days[0].day()));
+        return visualize(
+            days[0],
+            &input,
+            args.part,
+            &params,
+            Duration::from_millis(args.delay),
+        );
+    }
+
+    let mut reports = Vec::new();
+    for day in days {
+        let input = args
+            .input
+            .clone()
+            .unwrap_or_else(|| default_input(day.day()));
+        let mut emit = |report: Report| {
+            if args.format == Format::Text {
+                print_report(&report);
+            }
+            reports.push(report);
+        };
+        if args.stream {
+            stream_day(day, &input, args.part, &params, &mut emit);
+        } else {
+            run_day(day, &input, args.part, &params, &mut emit);
+        }
+    }
+
+    if args.format == Format::Json {
+        print_json(&reports);
+    }
+    reports.iter().all(|report| report.error.is_none())
+}
+
+fn print_report(report: &Report) {
+    match (report.part, &report.answer, &report.error) {
+        (Some(part), Some(answer), _) => println!("Day {}, part {}: {}", report.day, part, answer),
+        (Some(part), None, Some(e)) => {
+            println!("Day {}, part {}: failed ({})", report.day, part, e)
+        }
+        (_, _, e) => eprintln!(
+            "Day {}: {}",
+            report.day,
+            e.as_deref().unwrap_or("no answer")
+        ),
+    }
+}
+
+fn print_json(reports: &[Report]) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(reports).expect("reports are serializable")
+    );
+}
+
+/// Solves the input of a day, passing a report for every part to `emit` as
+/// soon as it is done.
+fn run_day(
+    day: &dyn DynSolution,
+    input: &Path,
+    part: Option<u8>,
+    params: &Params,
+    emit: &mut dyn FnMut(Report),
+) {
+    let mut report = Report::new(day.day(), input);
+    let mut reader = match open(input) {
+        Ok(reader) => reader,
+        Err(e) => {
+            report.error = Some(format!("cannot open {}: {}", input.display(), e));
+            return emit(report);
+        }
+    };
+
+    let start = Instant::now();
+    let parsed = day.parse(&mut reader);
+    let parse_ns = nanos(start.elapsed());
+    report.parse_ns = Some(parse_ns);
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            report.error = Some(format!("invalid input: {}", e));
+            return emit(report);
+        }
+    };
+
+    for current in 1..=day.parts() {
+        if part.is_some_and(|part| part != current) {
+            continue;
+        }
+
+        let mut report = Report {
+            part: Some(current),
+            answer_type: Some(day.answer_type(current)),
+            parse_ns: Some(parse_ns),
+            ..Report::new(day.day(), input)
+        };
+        let start = Instant::now();
+        let solved = catch_solver(|| day.solve_with(parsed.as_ref(), current, params));
+        match solved {
+            Ok(answer) => report.answer = Some(answer),
+            Err(e) => report.error = Some(panic_message(&*e).to_owned()),
+        }
+        report.solve_ns = Some(nanos(start.elapsed()));
+        emit(report);
+    }
+}
+
+/// Solves both parts in a single pass with [`DynSolution::stream`]. Parsing
+/// and solving can't be told apart, both parts report the whole pass.
+fn stream_day(
+    day: &dyn DynSolution,
+    input: &Path,
+    part: Option<u8>,
+    params: &Params,
+    emit: &mut dyn FnMut(Report),
+) {
+    let mut report = Report::new(day.day(), input);
+    let mut reader = match open(input) {
+        Ok(reader) => reader,
+        Err(e) => {
+            report.error = Some(format!("cannot open {}: {}", input.display(), e));
+            return emit(report);
+        }
+    };
+
+    let start = Instant::now();
+    let answers = catch_solver(|| day.stream(&mut reader, params));
+    let solve_ns = nanos(start.elapsed());
+    let answers = match answers {
+        Ok(Some(Ok(answers))) => answers,
+        Ok(Some(Err(e))) => {
+            report.error = Some(format!("invalid input: {}", e));
+            return emit(report);
+        }
+        Ok(None) => {
+            report.error = Some("no single pass solution".to_owned());
+            return emit(report);
+        }
+        Err(e) => {
+            report.error = Some(panic_message(&*e).to_owned());
+            return emit(report);
+        }
+    };
+
+    for (current, answer) in (1..=day.parts()).zip(answers) {
+        if part.is_some_and(|part| part != current) {
+            continue;
+        }
+        emit(Report {
+            part: Some(current),
+            answer: Some(answer),
+            answer_type: Some(day.answer_type(current)),
+            solve_ns: Some(solve_ns),
+            ..Report::new(day.day(), input)
+        });
+    }
+}
+
+/// A buffered reader of `input`, `-` being stdin.
+pub fn open(input: &Path) -> io::Result<Box<dyn BufRead>> {
+    if input == Path::new("-") {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(input)?)))
+    }
+}
+
+/// Reads and parses `input`, `-` being stdin, reporting failures on stderr.
+pub fn load(day: &dyn DynSolution, input: &Path) -> Option<Box<dyn Any>> {
+    let data = if input == Path::new("-") {
+        let mut data = Vec::new();
+        io::stdin().read_to_end(&mut data).map(|_| data)
+    } else {
+        fs::read(input)
+    };
+    let parsed = match data {
+        Ok(data) => day.parse(&mut data.as_slice()),
+        Err(e) => {
+            eprintln!("Day {}: cannot read {}: {}", day.day(), input.display(), e);
+            return None;
+        }
+    };
+    match parsed {
+        Ok(parsed) => Some(parsed),
+        Err(e) => {
+            eprintln!("Day {}: invalid input: {}", day.day(), e);
+            None
+        }
+    }
+}
+
+/// Plays the animation of every selected part, followed by its answer.
+fn visualize(
+    day: &dyn DynSolution,
+    input: &Path,
+    part: Option<u8>,
+    params: &Params,
+    delay: Duration,
+) -> bool {
+    let parsed = match load(day, input) {
+        Some(parsed) => parsed,
+        None => return false,
+    };
+
+    let colour = env::var_os("NO_COLOR").is_none();
+    let mut ok = true;
+    for current in 1..=day.parts() {
+        if part.is_some_and(|part| part != current) {
+            continue;
+        }
+        let frames = match render::animation(day.day(), parsed.as_ref(), current, params) {
+            Some(frames) => frames,
+            None => {
+                eprintln!("Day {} has no visualization", day.day());
+                return false;
+            }
+        };
+
+        let played = catch_solver(|| {
+            for (generation, frame) in frames.enumerate() {
+                // Clears the screen and moves the cursor to the top left corner.
+                print!("\x1b[H\x1b[2J");
+                println!(
+                    "Day {}, part {}, generation {}",
+                    day.day(),
+                    current,
+                    generation
+                );
+                if colour {
+                    print!("{}", render::ansi(&frame));
+                } else {
+                    println!("{}", frame);
+                }
+                let _ = io::stdout().flush();
+                thread::sleep(delay);
+            }
+            day.solve_with(parsed.as_ref(), current, params)
+        });
+        match played {
+            Ok(answer) => println!("Day {}, part {}: {}", day.day(), current, answer),
+            Err(e) => {
+                println!(
+                    "Day {}, part {}: failed ({})",
+                    day.day(),
+                    current,
+                    panic_message(&*e)
+                );
+                ok = false;
+            }
+        }
+    }
+    ok
+}
+
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+fn run_all(args: &RunArgs) -> bool {
+    let started = Instant::now();
+    let mut jobs = Vec::new();
+    for &day in select_days(None).unwrap_or_default().iter() {
+        if !args.all_inputs {
+            jobs.push((day, default_input(day.day())));
+            continue;
+        }
+        match inputs::list(Path::new(inputs::DIR), day.day()) {
+            Ok(day_inputs) => jobs.extend(day_inputs.into_iter().map(|input| (day, input))),
+            Err(e) => eprintln!("Day {}: cannot list inputs: {}", day.day(), e),
+        }
+    }
+
+    let threads = match args.jobs {
+        Some(jobs) => jobs as usize,
+        None => thread::available_parallelism().map_or(1, |threads| threads.get()),
+    };
+    let timeout = Duration::from_secs(args.timeout);
+    let mut reports = run_parallel(jobs, args.part, threads, timeout);
+    reports.sort_by(|a, b| (a.day, &a.input, a.part).cmp(&(b.day, &b.input, b.part)));
+
+    match args.format {
+        Format::Text => print_summary(&reports, started.elapsed()),
+        Format::Json => print_json(&reports),
+    }
+    reports.iter().all(|report| report.error.is_none())
+}
+
+/// A day that is being solved by [`run_parallel`].
+struct Job {
+    day: &'static dyn DynSolution,
+    input: PathBuf,
+    deadline: Instant,
+    solved: Vec<u8>,
+}
+
+/// Solves every input on a thread of its own, at most `threads` at a time. An
+/// input still being solved after `timeout` is reported as timed out in the
+/// first part without an answer, and its thread is left behind so that the
+/// remaining inputs don't have to wait for it.
+///
+/// A thread can't be stopped from the outside, so one left behind keeps
+/// running until its solver returns, next to the `threads` solving the
+/// remaining inputs. More than `threads` threads are then busy at once, and
+/// the later inputs are timed with fewer CPUs to spare.
+fn run_parallel(
+    queue: Vec<(&'static dyn DynSolution, PathBuf)>,
+    part: Option<u8>,
+    threads: usize,
+    timeout: Duration,
+) -> Vec<Report> {
+    let (sender, receiver) = mpsc::channel();
+    let mut queue = queue.into_iter().enumerate();
+    let mut running: HashMap<usize, Job> = HashMap::new();
+    let mut reports = Vec::new();
+
+    loop {
+        while running.len() < threads {
+            let (id, (day, input)) = match queue.next() {
+                Some(next) => next,
+                None => break,
+            };
+            let job = Job {
+                day,
+                input: input.clone(),
+                deadline: Instant::now() + timeout,
+                solved: Vec::new(),
+            };
+            running.insert(id, job);
+
+            let sender = sender.clone();
+            thread::spawn(move || {
+                let solved = catch_solver(|| {
+                    run_day(day, &input, part, &Params::default(), &mut |report| {
+                        let _ = sender.send((id, Some(report)));
+                    })
+                });
+                if let Err(e) = solved {
+                    let report = Report {
+                        error: Some(panic_message(&*e).to_owned()),
+                        ..Report::new(day.day(), &input)
+                    };
+                    let _ = sender.send((id, Some(report)));
+                }
+                let _ = sender.send((id, None));
+            });
+        }
+
+        let deadline = match running.values().map(|job| job.deadline).min() {
+            Some(deadline) => deadline,
+            None => return reports,
+        };
+        match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok((id, Some(report))) => {
+                // Reports of abandoned jobs come too late to be counted.
+                if let Some(job) = running.get_mut(&id) {
+                    job.solved.extend(report.part);
+                    reports.push(report);
+                }
+            }
+            Ok((id, None)) => {
+                running.remove(&id);
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let now = Instant::now();
+                let expired: Vec<_> = running
+                    .iter()
+                    .filter(|(_, job)| job.deadline <= now)
+                    .map(|(&id, _)| id)
+                    .collect();
+                for id in expired {
+                    let job = running.remove(&id).expect("expired job is running");
+                    let stuck = (1..=job.day.parts()).find(|current| {
+                        part.is_none_or(|part| part == *current) && !job.solved.contains(current)
+                    });
+                    reports.push(Report {
+                        part: stuck,
+                        error: Some(format!("timed out after {}s", timeout.as_secs())),
+                        timed_out: true,
+                        ..Report::new(job.day.day(), &job.input)
+                    });
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => unreachable!("a sender is kept"),
+        }
+    }
+}
+
+fn print_summary(reports: &[Report], elapsed: Duration) {
+    println!(
+        "{:>3}  {:<14}  {:>4}  {:>9}  {:>9}  Answer",
+        "Day", "Input", "Part", "Parse", "Solve"
+    );
+    let elapsed_cell = |nanos: Option<u64>| {
+        nanos.map_or_else(String::new, |nanos| {
+            Elapsed(Duration::from_nanos(nanos)).to_string()
+        })
+    };
+    for report in reports {
+        let outcome = match (&report.answer, &report.error) {
+            (Some(answer), _) => answer.clone(),
+            (None, Some(e)) if report.timed_out => e.clone(),
+            (None, Some(e)) => format!("failed ({})", e),
+            (None, None) => String::new(),
+        };
+        println!(
+            "{:>3}  {:<14}  {:>4}  {:>9}  {:>9}  {}",
+            report.day,
+            report
+                .input
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy(),
+            report
+                .part
+                .map_or_else(|| "-".to_owned(), |part| part.to_string()),
+            elapsed_cell(report.parse_ns),
+            elapsed_cell(report.solve_ns),
+            outcome
+        );
+    }
+
+    let answers = reports
+        .iter()
+        .filter(|report| report.answer.is_some())
+        .count();
+    let timeouts = reports.iter().filter(|report| report.timed_out).count();
+    let failures = reports
+        .iter()
+        .filter(|report| report.error.is_some())
+        .count()
+        - timeouts;
+    println!(
+        "{} answers, {} failures, {} timeouts in {}",
+        answers,
+        failures,
+        timeouts,
+        Elapsed(elapsed)
+    );
+}
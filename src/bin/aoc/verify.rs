@@ -0,0 +1,248 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use advent_of_rust_2020::{
+    answers::{self, Answers, Verdict},
+    inputs,
+    registry::DynSolution,
+};
+use clap::Args;
+
+use crate::{catch_solver, collect_with_timeout, default_input, panic_message, select_days};
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    /// Day to verify [default: all days]
+    #[arg(long)]
+    day: Option<u8>,
+    /// Seconds after which an input is abandoned
+    #[arg(long, default_value_t = 60)]
+    timeout: u64,
+}
+
+#[derive(Args)]
+pub struct RecordArgs {
+    /// Day the input belongs to
+    #[arg(long)]
+    day: u8,
+    /// Puzzle input [default: inputs/dayN/1.txt]
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Record only this part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Record this answer instead of solving, `-` if the part doesn't apply to the input
+    #[arg(long, requires = "part", allow_hyphen_values = true)]
+    answer: Option<String>,
+    /// Replace answers recorded before
+    #[arg(long)]
+    force: bool,
+}
+
+pub fn verify(args: &VerifyArgs) -> bool {
+    let days = match select_days(args.day) {
+        Some(days) => days,
+        None => return false,
+    };
+
+    let (mut passed, mut failed, mut unknown, mut errors) = (0, 0, 0, 0);
+    for day in days {
+        let recorded = match Answers::load(Path::new(answers::DIR), day.day()) {
+            Ok(recorded) => recorded,
+            Err(e) => {
+                eprintln!("Day {}: cannot load answers: {}", day.day(), e);
+                errors += 1;
+                continue;
+            }
+        };
+        let day_inputs = match inputs::list(Path::new(inputs::DIR), day.day()) {
+            Ok(day_inputs) => day_inputs,
+            Err(e) => {
+                eprintln!("Day {}: cannot list inputs: {}", day.day(), e);
+                errors += 1;
+                continue;
+            }
+        };
+
+        for path in day_inputs {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let data = match fs::read(&path) {
+                Ok(data) => data,
+                Err(e) => {
+                    eprintln!("Day {}, {}: cannot read: {}", day.day(), name, e);
+                    errors += 1;
+                    continue;
+                }
+            };
+            let hash = answers::hash_input(&data);
+            let mut known = recorded.clone();
+            if let Err(e) = known.load_sidecar(&path, &hash) {
+                eprintln!("Day {}, {}: cannot load answers: {}", day.day(), name, e);
+                errors += 1;
+                continue;
+            }
+            let parts: Vec<_> = (1..=day.parts())
+                .filter(|&part| known.get(&hash, part) != Some(answers::NOT_APPLICABLE))
+                .collect();
+            if parts.is_empty() {
+                continue;
+            }
+
+            let timeout = Duration::from_secs(args.timeout);
+            let (solved, timed_out) = solve_in_background(day, data, parts.clone(), timeout);
+            for (part, answer) in &solved {
+                let label = match part {
+                    Some(part) => format!("Day {}, {}, part {}", day.day(), name, part),
+                    None => format!("Day {}, {}", day.day(), name),
+                };
+                match (part, answer) {
+                    (Some(part), Ok(answer)) => match known.check(&hash, *part, answer) {
+                        Verdict::Pass => {
+                            println!("{}: pass", label);
+                            passed += 1;
+                        }
+                        Verdict::Fail(expected) => {
+                            println!("{}: FAIL (expected {}, got {})", label, expected, answer);
+                            failed += 1;
+                        }
+                        Verdict::Unknown => {
+                            println!("{}: unknown ({}, input hash {})", label, answer, hash);
+                            unknown += 1;
+                        }
+                    },
+                    (_, Err(message)) | (None, Ok(message)) => {
+                        println!("{}: {}", label, message);
+                        errors += 1;
+                    }
+                }
+            }
+            if timed_out {
+                let part = parts[solved.len().min(parts.len() - 1)];
+                println!(
+                    "Day {}, {}, part {}: timed out after {}s",
+                    day.day(),
+                    name,
+                    part,
+                    args.timeout
+                );
+                errors += 1;
+            }
+        }
+    }
+
+    println!(
+        "{} passed, {} failed, {} unknown, {} errors",
+        passed, failed, unknown, errors
+    );
+    failed == 0 && errors == 0
+}
+
+pub fn record(args: &RecordArgs) -> bool {
+    let day = match select_days(Some(args.day)) {
+        Some(days) => days[0],
+        None => return false,
+    };
+    if let Some(part) = args.part.filter(|&part| part > day.parts()) {
+        eprintln!("Day {} has no part {}, only one", args.day, part);
+        return false;
+    }
+    let path = args
+        .input
+        .clone()
+        .unwrap_or_else(|| default_input(args.day));
+    let data = match fs::read(&path) {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("Day {}: cannot read {}: {}", args.day, path.display(), e);
+            return false;
+        }
+    };
+    let dir = Path::new(answers::DIR);
+    let mut known = match Answers::load(dir, args.day) {
+        Ok(known) => known,
+        Err(e) => {
+            eprintln!("Day {}: cannot load answers: {}", args.day, e);
+            return false;
+        }
+    };
+
+    let hash = answers::hash_input(&data);
+    let mut new = Vec::new();
+    if let (Some(part), Some(answer)) = (args.part, &args.answer) {
+        new.push((part, answer.clone()));
+    } else {
+        let input = match day.parse(&mut data.as_slice()) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {}: invalid input: {}", args.day, e);
+                return false;
+            }
+        };
+        for part in 1..=day.parts() {
+            if args.part.is_some_and(|only| only != part) {
+                continue;
+            }
+            match catch_solver(|| day.solve(input.as_ref(), part)) {
+                Ok(answer) => new.push((part, answer)),
+                Err(e) => println!(
+                    "Day {}, part {}: failed ({})",
+                    args.day,
+                    part,
+                    panic_message(&*e)
+                ),
+            }
+        }
+    }
+
+    let mut ok = true;
+    for (part, answer) in new {
+        match known.get(&hash, part) {
+            Some(old) if old != answer && !args.force => {
+                eprintln!(
+                    "Day {}, part {}: already recorded as {}, use --force to replace it",
+                    args.day, part, old
+                );
+                ok = false;
+            }
+            _ => {
+                known.insert(&hash, part, &answer);
+                println!("Day {}, part {}: recorded {}", args.day, part, answer);
+            }
+        }
+    }
+
+    if let Err(e) = known.save(dir) {
+        eprintln!("Day {}: cannot save answers: {}", args.day, e);
+        return false;
+    }
+    ok
+}
+
+/// Answer of a part, or an error with no part if the input is invalid.
+type Solved = (Option<u8>, Result<String, String>);
+
+/// Parses `data` and solves the given parts, see [`collect_with_timeout`].
+fn solve_in_background(
+    day: &'static dyn DynSolution,
+    data: Vec<u8>,
+    parts: Vec<u8>,
+    timeout: Duration,
+) -> (Vec<Solved>, bool) {
+    collect_with_timeout(timeout, move |sender| {
+        let input = match day.parse(&mut data.as_slice()) {
+            Ok(input) => input,
+            Err(e) => {
+                let _ = sender.send((None, Err(format!("invalid input: {}", e))));
+                return;
+            }
+        };
+        for part in parts {
+            let answer = catch_solver(|| day.solve(input.as_ref(), part))
+                .map_err(|e| format!("failed ({})", panic_message(&*e)));
+            let _ = sender.send((Some(part), answer));
+        }
+    })
+}
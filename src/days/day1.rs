@@ -0,0 +1,489 @@
+use itertools::Itertools;
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    fmt,
+    io::BufRead,
+    ops::ControlFlow,
+};
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    params, parse, Answers, Error, Param, Params, Solution,
+};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<i32>;
+    type Answer1 = Product;
+    type Answer2 = Product;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_numbers(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        Self::part1_with(input, &Params::default())
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        Self::part2_with(input, &Params::default())
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+
+    const PARAMS: &'static [Param] = &[TARGET];
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Self::Answer1 {
+        Product(part1(input, TARGET.get(params)))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Self::Answer2 {
+        Product(part2(input, TARGET.get(params)))
+    }
+
+    fn stream<R: BufRead>(reader: R, params: &Params) -> Option<Result<Answers<Self>, Error>> {
+        let answers = stream(reader, TARGET.get(params))
+            .map(|(pair, triple)| (Product(pair), Product(triple)));
+        Some(answers)
+    }
+}
+
+pub const TARGET: Param = Param {
+    name: "target",
+    default: "2020",
+    help: "Sum of the entries to find",
+    check: params::number::<i32>,
+};
+
+/// Product of the entries summing to the target, `none` if no entries do or
+/// their product overflows an `i64`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Product(pub Option<i64>);
+
+impl fmt::Display for Product {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(product) => write!(f, "{}", product),
+            None => write!(f, "none"),
+        }
+    }
+}
+
+/// Every way to pick `k` entries summing to `target`, as their positions in
+/// increasing order. Entries with equal values are told apart by position,
+/// but none is picked twice.
+///
+/// Takes O(n^(k-1)) time, the last two entries of each pick being matched up
+/// through a map of the values seen so far.
+pub fn k_sum(numbers: &[i32], k: usize, target: i32) -> Vec<Vec<usize>> {
+    let mut picks = Vec::new();
+    let _ = search::<()>(numbers, k, target.into(), 0, &mut Vec::new(), &mut |pick| {
+        picks.push(pick.to_vec());
+        ControlFlow::Continue(())
+    });
+    picks
+}
+
+/// The first pick of [`k_sum`], without looking for the others.
+pub fn first_k_sum(numbers: &[i32], k: usize, target: i32) -> Option<Vec<usize>> {
+    match search(numbers, k, target.into(), 0, &mut Vec::new(), &mut |pick| {
+        ControlFlow::Break(pick.to_vec())
+    }) {
+        ControlFlow::Break(pick) => Some(pick),
+        ControlFlow::Continue(()) => None,
+    }
+}
+
+/// Extends `picked` by `k` positions from `start` on whose entries sum to
+/// `target`, passing every complete pick to `found` until it breaks.
+fn search<B>(
+    numbers: &[i32],
+    k: usize,
+    target: i64,
+    start: usize,
+    picked: &mut Vec<usize>,
+    found: &mut dyn FnMut(&[usize]) -> ControlFlow<B>,
+) -> ControlFlow<B> {
+    match k {
+        0 if target == 0 => found(picked),
+        0 => ControlFlow::Continue(()),
+        1 => {
+            for (i, &number) in numbers.iter().enumerate().skip(start) {
+                if i64::from(number) == target {
+                    picked.push(i);
+                    let flow = found(picked);
+                    picked.pop();
+                    flow?;
+                }
+            }
+            ControlFlow::Continue(())
+        }
+        2 => {
+            let mut seen: HashMap<i64, Vec<usize>> = HashMap::new();
+            for (j, &number) in numbers.iter().enumerate().skip(start) {
+                let number = i64::from(number);
+                for &i in seen.get(&(target - number)).into_iter().flatten() {
+                    picked.extend([i, j]);
+                    let flow = found(picked);
+                    picked.truncate(picked.len() - 2);
+                    flow?;
+                }
+                seen.entry(number).or_default().push(j);
+            }
+            ControlFlow::Continue(())
+        }
+        _ => {
+            for (i, &number) in numbers.iter().enumerate().skip(start) {
+                picked.push(i);
+                let flow = search(
+                    numbers,
+                    k - 1,
+                    target - i64::from(number),
+                    i + 1,
+                    picked,
+                    found,
+                );
+                picked.pop();
+                flow?;
+            }
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+/// Product of the entries at `positions`, `None` if it overflows.
+fn product(numbers: &[i32], positions: &[usize]) -> Option<i64> {
+    multiply(positions.iter().map(|&i| numbers[i]))
+}
+
+/// Product of `values`, `None` if it overflows.
+fn multiply(values: impl IntoIterator<Item = i32>) -> Option<i64> {
+    values
+        .into_iter()
+        .try_fold(1i64, |product, value| product.checked_mul(value.into()))
+}
+
+pub fn part1(numbers: &[i32], sum: i32) -> Option<i64> {
+    first_k_sum(numbers, 2, sum).and_then(|pair| product(numbers, &pair))
+}
+
+pub fn part2(numbers: &[i32], sum: i32) -> Option<i64> {
+    first_k_sum(numbers, 3, sum).and_then(|triple| product(numbers, &triple))
+}
+
+/// Both answers while reading the entries, picking the same pair and triple
+/// as [`first_k_sum`]. The pair is the first one completed, the triple the
+/// one with the earliest first entry, then the earliest last entry. For that
+/// only the first and last position of every value is kept.
+pub fn stream<R: BufRead>(reader: R, sum: i32) -> Result<(Option<i64>, Option<i64>), Error> {
+    let mut pair = None;
+    let mut triple: Option<(usize, [i32; 3])> = None;
+    let mut seen: HashMap<i32, (usize, usize)> = HashMap::new();
+    for (position, number) in parse::each_number(Day1::DAY, reader).enumerate() {
+        let number: i32 = number?;
+        let rest = i64::from(sum) - i64::from(number);
+        if !matches!(triple, Some((0, _))) {
+            let earliest = seen
+                .iter()
+                .filter_map(|(&other, &(first, _))| {
+                    let third = i32::try_from(rest - i64::from(other)).ok()?;
+                    match seen.get(&third) {
+                        Some(&(_, last)) if last > first => Some((first, [other, third, number])),
+                        _ => None,
+                    }
+                })
+                .min_by_key(|&(first, _)| first);
+            if let Some(found) = earliest {
+                if triple.is_none_or(|(first, _)| found.0 < first) {
+                    triple = Some(found);
+                }
+            }
+        }
+        if pair.is_none() {
+            if let Some(other) = i32::try_from(rest)
+                .ok()
+                .filter(|other| seen.contains_key(other))
+            {
+                pair = Some(multiply([number, other]));
+            }
+        }
+        seen.entry(number)
+            .and_modify(|(_, last)| *last = position)
+            .or_insert((position, position));
+    }
+    Ok((
+        pair.flatten(),
+        triple.and_then(|(_, values)| multiply(values)),
+    ))
+}
+
+/// An entry of the expense report and the line it is on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Entry {
+    pub line: usize,
+    pub value: i32,
+}
+
+/// Entries summing to the target and their product, `None` if it overflows.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Pick {
+    pub entries: Vec<Entry>,
+    pub product: Option<i64>,
+}
+
+/// Every pair and triple of entries summing to the target, and the entries
+/// which are in none of them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Audit {
+    pub target: i32,
+    pub entries: usize,
+    pub pairs: Vec<Pick>,
+    pub triples: Vec<Pick>,
+    /// Pairs with different values, entries of equal value being
+    /// interchangeable.
+    pub distinct_pairs: usize,
+    pub distinct_triples: usize,
+    pub unused: Vec<Entry>,
+}
+
+impl Audit {
+    pub fn new(entries: &[Entry], target: i32) -> Self {
+        let numbers: Vec<i32> = entries.iter().map(|entry| entry.value).collect();
+        let mut used = vec![false; entries.len()];
+        let mut picks = |k| -> (Vec<Pick>, usize) {
+            let picks = k_sum(&numbers, k, target);
+            for &i in picks.iter().flatten() {
+                used[i] = true;
+            }
+            let distinct = picks
+                .iter()
+                .map(|pick| pick.iter().map(|&i| numbers[i]).sorted().collect_vec())
+                .unique()
+                .count();
+            let picks = picks
+                .iter()
+                .map(|pick| Pick {
+                    entries: pick.iter().map(|&i| entries[i]).collect(),
+                    product: product(&numbers, pick),
+                })
+                .collect();
+            (picks, distinct)
+        };
+        let (pairs, distinct_pairs) = picks(2);
+        let (triples, distinct_triples) = picks(3);
+
+        Audit {
+            target,
+            entries: entries.len(),
+            pairs,
+            triples,
+            distinct_pairs,
+            distinct_triples,
+            unused: entries
+                .iter()
+                .zip(used)
+                .filter(|(_, used)| !used)
+                .map(|(&entry, _)| entry)
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Pick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = self.entries.iter().map(|entry| entry.line).join(", ");
+        let values = self.entries.iter().map(|entry| entry.value).join(" * ");
+        match self.product {
+            Some(product) => write!(f, "lines {}: {} = {}", lines, values, product),
+            None => write!(f, "lines {}: {} overflows", lines, values),
+        }
+    }
+}
+
+impl fmt::Display for Audit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} entries, target {}", self.entries, self.target)?;
+        let sections = [
+            ("pairs", &self.pairs, self.distinct_pairs),
+            ("triples", &self.triples, self.distinct_triples),
+        ];
+        for (name, picks, distinct) in sections.iter() {
+            writeln!(f, "{} {} ({} distinct)", picks.len(), name, distinct)?;
+            for pick in picks.iter() {
+                writeln!(f, "  {}", pick)?;
+            }
+        }
+        writeln!(f, "{} entries in no pair or triple", self.unused.len())?;
+        for entry in self.unused.iter() {
+            writeln!(f, "  line {}: {}", entry.line, entry.value)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn read_numbers<R: BufRead>(reader: R) -> Result<Vec<i32>, Error> {
+    parse::numbers(Day1::DAY, reader)
+}
+
+/// The entries with their line numbers, blank lines are skipped.
+pub fn read_entries<R: BufRead>(reader: R) -> Result<Vec<Entry>, Error> {
+    let mut entries = Vec::new();
+    for line in parse::lines(reader) {
+        let (num, line) = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let value = parse::number(Day1::DAY, &line, &line).map_err(|e| e.on_line(num))?;
+        entries.push(Entry { line: num, value });
+    }
+    Ok(entries)
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "entries",
+    sizes: 5..=500,
+    default_size: 200,
+    build: generate,
+};
+
+/// A pair and a triple of entries below 1010 summing to 2020, hidden among
+/// entries above it. Those can't be part of a pair, or of a triple with
+/// fewer than two of the small entries, which rules out the few that would.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let (pair, triple) = loop {
+        let a = rng.range(20..=1009) as i32;
+        let (c, d) = (rng.range(20..=1009) as i32, rng.range(20..=1009) as i32);
+        let (pair, triple) = ([a, 2020 - a], [c, d, 2020 - c - d]);
+        if !(20..=1009).contains(&triple[2]) {
+            continue;
+        }
+
+        // Every way the solution could combine the entries, one used twice
+        // included, has to lead to the planted pair or triple.
+        let entries: Vec<_> = pair.iter().chain(&triple).copied().collect();
+        let sorted = |mut entries: Vec<i32>| {
+            entries.sort_unstable();
+            entries
+        };
+        let distinct = entries.iter().unique().count() == entries.len();
+        let sums_unique = entries.iter().tuple_combinations().all(|(&x, &y)| {
+            let third = 2020 - x - y;
+            (x + y != 2020 || sorted(vec![x, y]) == sorted(pair.to_vec()))
+                && (!entries.contains(&third)
+                    || sorted(vec![x, y, third]) == sorted(triple.to_vec()))
+        });
+        if distinct && sums_unique && entries.iter().all(|&x| !entries.contains(&(2020 - 2 * x))) {
+            break (pair, triple);
+        }
+    };
+
+    let small = [pair[0], triple[0], triple[1], triple[2]];
+    let excluded: HashSet<i32> = small
+        .iter()
+        .flat_map(|&x| small.iter().map(move |&y| 2020 - x - y))
+        .chain(small.iter().map(|&x| 2020 - x))
+        .collect();
+    let mut large: Vec<i32> = (1011..2020).filter(|x| !excluded.contains(x)).collect();
+    rng.shuffle(&mut large);
+
+    let mut entries: Vec<i32> = pair
+        .iter()
+        .chain(&triple)
+        .chain(&large[..size - 5])
+        .copied()
+        .collect();
+    rng.shuffle(&mut entries);
+    Generated::new(generate::lines(entries))
+        .with_answer(1, pair[0] * pair[1])
+        .with_answer(2, triple.iter().product::<i32>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn part1_sample() {
+        let numbers = vec![1721, 979, 366, 299, 675, 1456];
+        assert_eq!(part1(&numbers, 2020), Some(514579))
+    }
+
+    #[test]
+    fn part2_sample() {
+        let numbers = vec![1721, 979, 366, 299, 675, 1456];
+        assert_eq!(part2(&numbers, 2020), Some(241861950))
+    }
+
+    #[test]
+    fn test_solution() {
+        let numbers =
+            read_numbers(BufReader::new(File::open("inputs/day1/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&numbers, 2020), Some(878724));
+        assert_eq!(part2(&numbers, 2020), Some(201251610));
+    }
+
+    #[test]
+    fn test_k_sum() {
+        let numbers = [1010, 5, 1010, 2015, 1000, 10];
+        assert_eq!(k_sum(&numbers, 2, 2020), vec![vec![0, 2], vec![1, 3]]);
+        assert_eq!(first_k_sum(&numbers, 3, 2020), Some(vec![0, 4, 5]));
+        assert_eq!(first_k_sum(&numbers, 2, 2030), None);
+        assert_eq!(Day1::part1(&numbers.to_vec()).to_string(), "1020100");
+        assert_eq!(Product(None).to_string(), "none");
+        assert_eq!(k_sum(&numbers, 1, 10), vec![vec![5]]);
+        assert_eq!(k_sum(&numbers, 0, 0), vec![Vec::<usize>::new()]);
+        assert_eq!(part1(&[1010, 7], 2020), None);
+        assert_eq!(part1(&[100_000, 100_000], 200_000), Some(10_000_000_000));
+        assert_eq!(part2(&[2_100_000; 3], 6_300_000), None);
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = "2\n3\n6\n1\n7\n";
+        let numbers = read_numbers(input.as_bytes()).unwrap();
+        assert_eq!(part2(&numbers, 10), Some(14));
+        assert_eq!(stream(input.as_bytes(), 10).unwrap(), (Some(21), Some(14)));
+    }
+
+    #[test]
+    fn test_audit() {
+        let entries = read_entries("1010\n\n5\n1010\n2015\n1000\n10\n1234\n".as_bytes()).unwrap();
+        let audit = Audit::new(&entries, 2020);
+        assert_eq!(audit.pairs.len(), 2);
+        assert_eq!(audit.distinct_pairs, 2);
+        assert_eq!(audit.triples.len(), 2);
+        assert_eq!(audit.distinct_triples, 1);
+        assert_eq!(
+            audit.unused,
+            [Entry {
+                line: 8,
+                value: 1234
+            }]
+        );
+        assert_eq!(
+            audit.to_string(),
+            "7 entries, target 2020\n\
+             2 pairs (2 distinct)\n  lines 1, 4: 1010 * 1010 = 1020100\n  lines 3, 5: 5 * 2015 = 10075\n\
+             2 triples (1 distinct)\n  lines 1, 6, 7: 1010 * 1000 * 10 = 10100000\n  lines 4, 6, 7: 1010 * 1000 * 10 = 10100000\n\
+             1 entries in no pair or triple\n  line 8: 1234\n"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn stream_agrees(numbers in prop::collection::vec(-5..20i32, 0..30), sum in -5..40i32) {
+            let input = generate::lines(&numbers);
+            prop_assert_eq!(
+                stream(input.as_bytes(), sum).unwrap(),
+                (part1(&numbers, sum), part2(&numbers, sum))
+            );
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use itertools::Itertools;
+use std::io::BufRead;
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    parse, Error, ParseError, Solution,
+};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_adapters(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+}
+
+pub fn part1(adapters: &[u32]) -> u32 {
+    let result = adapters
+        .iter()
+        .tuple_windows()
+        .map(|(first, second)| second - first)
+        .fold((0, 0), |sum, v| {
+            if v == 1 {
+                (sum.0 + 1, sum.1)
+            } else {
+                (sum.0, sum.1 + 1)
+            }
+        });
+    result.0 * result.1
+}
+pub fn part2(adapters: &[u32]) -> u64 {
+    let mut last_adapter = 0;
+    let mut current_chunk: Vec<u32> = Vec::new();
+    let mut result = 1;
+    for adapter in adapters.iter() {
+        if adapter - last_adapter == 3 || current_chunk.len() == 5 {
+            if current_chunk.len() == 3 {
+                result *= 2;
+            } else if current_chunk.len() == 4 {
+                result *= 4;
+            } else if current_chunk.len() == 5 {
+                result *= 7;
+            }
+            current_chunk.clear();
+            current_chunk.push(*adapter);
+        } else {
+            current_chunk.push(*adapter);
+        }
+        last_adapter = *adapter;
+    }
+    result
+}
+
+pub fn read_adapters<R: BufRead>(reader: R) -> Result<Vec<u32>, Error> {
+    let mut adapters: Vec<u32> = parse::numbers(Day10::DAY, reader)?;
+
+    let device = adapters
+        .iter()
+        .max()
+        .map_or(Some(3), |max| max.checked_add(3))
+        .ok_or_else(|| ParseError::end_of_input(Day10::DAY, "adapter joltage too high"))?;
+    adapters.push(0);
+    adapters.push(device);
+    adapters.sort_unstable();
+    Ok(adapters)
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "adapters",
+    sizes: 5..=100,
+    default_size: 100,
+    build: generate,
+};
+
+/// Adapters one or three jolts apart, with at most four in a row one jolt
+/// apart as in the puzzle.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    // Arrangements of a run of adapters one jolt apart, by the number of
+    // one jolt differences.
+    const ARRANGEMENTS: [u64; 5] = [1, 1, 2, 4, 7];
+    let (mut joltage, mut run, mut threes) = (0, 0, 0);
+    let mut arrangements = 1;
+    let mut adapters = Vec::new();
+    for _ in 0..size {
+        if run < 4 && rng.chance(70) {
+            joltage += 1;
+            run += 1;
+        } else {
+            joltage += 3;
+            threes += 1;
+            arrangements *= ARRANGEMENTS[run];
+            run = 0;
+        }
+        adapters.push(joltage);
+    }
+    arrangements *= ARRANGEMENTS[run];
+    let ones = size - threes;
+
+    rng.shuffle(&mut adapters);
+    Generated::new(generate::lines(adapters))
+        .with_answer(1, ones * (threes + 1))
+        .with_answer(2, arrangements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let adapters =
+            read_adapters(BufReader::new(File::open("inputs/day10/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&adapters), 2176);
+        assert_eq!(part2(&adapters), 18512297918464);
+    }
+}
@@ -0,0 +1,148 @@
+use std::{collections::HashSet, io::BufRead};
+
+use crate::{
+    automaton::{Automaton, Neighbourhood, Rule, Square},
+    generate::{Generated, Generator, Rng},
+    grid::{Grid, Position},
+    render::{Cell, Frame, Rgb},
+    Error, Params, Solution,
+};
+
+/// Rounds after which the seats are considered to never settle.
+const MAX_ROUNDS: usize = 10_000;
+
+#[derive(Clone, PartialEq)]
+pub struct Layout {
+    layout: Grid<char>,
+}
+
+impl Layout {
+    /// The seats as cells of an automaton, with occupied seats alive. An
+    /// empty seat is taken when no seat around is occupied, an occupied one
+    /// is left when `crowded` seats around are occupied as well.
+    pub fn automaton(&self, neighbourhood: Neighbourhood, crowded: usize) -> Automaton<Square> {
+        let seats = self.layout.map(|&square| square != '.');
+        let occupied = self
+            .layout
+            .iter()
+            .filter(|(_, &square)| square == '#')
+            .map(|(position, _)| position);
+        Automaton::new(
+            Square::new(&seats, neighbourhood),
+            Rule::new(vec![0], 0..crowded),
+            occupied,
+        )
+    }
+
+    /// Occupied seats once nobody moves anymore.
+    fn settle(&self, part: u8) -> u32 {
+        let (neighbourhood, crowded) = rules(part);
+        let mut automaton = self.automaton(neighbourhood, crowded);
+        automaton
+            .run_until_stable(MAX_ROUNDS)
+            .unwrap_or_else(|| panic!("seats still change after {} rounds", MAX_ROUNDS));
+        automaton.population() as u32
+    }
+
+    /// The floor in grey, empty seats in green and occupied ones in red.
+    pub fn frame(&self, occupied: &HashSet<Position>) -> Frame {
+        Grid::from_fn(
+            self.layout.width(),
+            self.layout.height(),
+            |position| match self.layout[position] {
+                '.' => Cell::new('.', Rgb(60, 60, 60)),
+                _ if occupied.contains(&position) => Cell::new('#', Rgb(200, 40, 40)),
+                _ => Cell::new('L', Rgb(40, 160, 60)),
+            },
+        )
+    }
+}
+
+/// Which seats people look at and how many occupied ones make them leave.
+fn rules(part: u8) -> (Neighbourhood, usize) {
+    match part {
+        1 => (Neighbourhood::Moore, 4),
+        _ => (Neighbourhood::LineOfSight, 5),
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Layout;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_layout(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+}
+
+pub fn part1(layout: &Layout) -> u32 {
+    layout.settle(1)
+}
+
+pub fn part2(layout: &Layout) -> u32 {
+    layout.settle(2)
+}
+
+/// Every round of the given part until nobody moves anymore.
+pub fn animation<'a>(
+    layout: &'a Layout,
+    part: u8,
+    _params: &Params,
+) -> Box<dyn Iterator<Item = Frame> + 'a> {
+    let (neighbourhood, crowded) = rules(part);
+    let automaton = layout.automaton(neighbourhood, crowded);
+    Box::new(automaton.animate(None, move |automaton| {
+        layout.frame(&automaton.live().collect())
+    }))
+}
+
+pub fn read_layout<R: BufRead>(reader: R) -> Result<Layout, Error> {
+    let layout = Grid::parse(
+        Day11::DAY,
+        reader,
+        |ch| Some(ch).filter(|ch| matches!(ch, '.' | 'L' | '#')),
+        "expected `.`, `L` or `#`",
+    )?;
+    Ok(Layout { layout })
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "rows",
+    sizes: 2..=200,
+    default_size: 95,
+    build: generate,
+};
+
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let layout = Grid::from_fn(size, size, |_| if rng.chance(70) { 'L' } else { '.' });
+    Generated::new(format!("{}\n", layout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let layout =
+            read_layout(BufReader::new(File::open("inputs/day11/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&layout), 2281);
+        assert_eq!(part2(&layout), 2085);
+    }
+}
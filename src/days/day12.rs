@@ -0,0 +1,273 @@
+use std::{fmt, io::BufRead, str::FromStr};
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    parse, Answers, Error, Params, ParseError, Solution,
+};
+
+pub type Instructions = Vec<Instruction>;
+
+#[derive(Default)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    fn distance(&self) -> i32 {
+        self.x.abs() + self.y.abs()
+    }
+
+    fn axis_rotate(&self, rad: f64) -> Self {
+        let sin = rad.sin() as i32;
+        let cos = rad.cos() as i32;
+        Point {
+            x: self.x * cos + self.y * sin,
+            y: -self.x * sin + self.y * cos,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Instruction {
+    North(i32),
+    South(i32),
+    East(i32),
+    West(i32),
+    TurnLeft(i32),
+    TurnRight(i32),
+    Forward(i32),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let action = s
+            .chars()
+            .next()
+            .ok_or_else(|| ParseError::line(Day12::DAY, s, "expected `<action><value>`"))?;
+        let val: i32 = parse::number(Day12::DAY, s, &s[action.len_utf8()..])?;
+
+        Ok(match action {
+            'N' => Instruction::North(val),
+            'S' => Instruction::South(val),
+            'E' => Instruction::East(val),
+            'W' => Instruction::West(val),
+            'L' => Instruction::TurnLeft(val),
+            'R' => Instruction::TurnRight(val),
+            'F' => Instruction::Forward(val),
+            _ => {
+                return Err(ParseError::within(
+                    Day12::DAY,
+                    s,
+                    &s[..action.len_utf8()],
+                    "unknown action",
+                ))
+            }
+        })
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (action, val) = match *self {
+            Instruction::North(val) => ('N', val),
+            Instruction::South(val) => ('S', val),
+            Instruction::East(val) => ('E', val),
+            Instruction::West(val) => ('W', val),
+            Instruction::TurnLeft(val) => ('L', val),
+            Instruction::TurnRight(val) => ('R', val),
+            Instruction::Forward(val) => ('F', val),
+        };
+        write!(f, "{}{}", action, val)
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Instructions;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_instructions(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+
+    fn stream<R: BufRead>(reader: R, _params: &Params) -> Option<Result<Answers<Self>, Error>> {
+        Some(stream(reader))
+    }
+}
+
+/// The ship of part 1, which the instructions move directly.
+#[derive(Default)]
+struct Ship {
+    position: Point,
+    direction: f64,
+}
+
+impl Ship {
+    fn follow(&mut self, instruction: &Instruction) {
+        let ship = &mut self.position;
+        match instruction {
+            Instruction::North(val) => {
+                ship.y += val;
+            }
+            Instruction::South(val) => {
+                ship.y -= val;
+            }
+            Instruction::East(val) => {
+                ship.x += val;
+            }
+            Instruction::West(val) => {
+                ship.x -= val;
+            }
+            Instruction::TurnLeft(val) => {
+                self.direction += f64::from(*val).to_radians();
+            }
+            Instruction::TurnRight(val) => {
+                self.direction -= f64::from(*val).to_radians();
+            }
+            Instruction::Forward(val) => {
+                ship.x += (self.direction.cos() as i32) * val;
+                ship.y += (self.direction.sin() as i32) * val;
+            }
+        }
+    }
+}
+
+/// The ship of part 2, which the instructions move towards a waypoint.
+struct Navigation {
+    ship: Point,
+    waypoint: Point,
+}
+
+impl Default for Navigation {
+    fn default() -> Self {
+        Navigation {
+            ship: Point::default(),
+            waypoint: Point { x: 10, y: 1 },
+        }
+    }
+}
+
+impl Navigation {
+    fn follow(&mut self, instruction: &Instruction) {
+        let waypoint = &mut self.waypoint;
+        match instruction {
+            Instruction::North(val) => {
+                waypoint.y += val;
+            }
+            Instruction::South(val) => {
+                waypoint.y -= val;
+            }
+            Instruction::East(val) => {
+                waypoint.x += val;
+            }
+            Instruction::West(val) => {
+                waypoint.x -= val;
+            }
+            Instruction::TurnLeft(val) => {
+                *waypoint = waypoint.axis_rotate(-f64::from(*val).to_radians());
+            }
+            Instruction::TurnRight(val) => {
+                *waypoint = waypoint.axis_rotate(f64::from(*val).to_radians());
+            }
+            Instruction::Forward(val) => {
+                self.ship.x += waypoint.x * val;
+                self.ship.y += waypoint.y * val;
+            }
+        }
+    }
+}
+
+pub fn part1(instructions: &[Instruction]) -> i32 {
+    let mut ship = Ship::default();
+    for instruction in instructions.iter() {
+        ship.follow(instruction);
+    }
+    ship.position.distance()
+}
+
+pub fn part2(instructions: &[Instruction]) -> i32 {
+    let mut navigation = Navigation::default();
+    for instruction in instructions.iter() {
+        navigation.follow(instruction);
+    }
+    navigation.ship.distance()
+}
+
+/// Both ships follow every instruction as it is read.
+pub fn stream<R: BufRead>(reader: R) -> Result<(i32, i32), Error> {
+    let (mut ship, mut navigation) = (Ship::default(), Navigation::default());
+    for instruction in parse::records(reader, Instruction::from_str) {
+        let instruction = instruction?;
+        ship.follow(&instruction);
+        navigation.follow(&instruction);
+    }
+    Ok((ship.position.distance(), navigation.ship.distance()))
+}
+
+pub fn read_instructions<R: BufRead>(reader: R) -> Result<Instructions, Error> {
+    parse::records(reader, Instruction::from_str).collect()
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "instructions",
+    sizes: 1..=2000,
+    default_size: 780,
+    build: generate,
+};
+
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let instructions = (0..size).map(|_| {
+        let turn = 90 * rng.range(1..=3) as i32;
+        let distance = rng.range(1..=100) as i32;
+        match rng.index(7) {
+            0 => Instruction::North(distance),
+            1 => Instruction::South(distance),
+            2 => Instruction::East(distance),
+            3 => Instruction::West(distance),
+            4 => Instruction::TurnLeft(turn),
+            5 => Instruction::TurnRight(turn),
+            _ => Instruction::Forward(distance),
+        }
+    });
+    Generated::new(generate::lines(instructions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let instructions =
+            read_instructions(BufReader::new(File::open("inputs/day12/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&instructions), 2458);
+        assert_eq!(part2(&instructions), 145117);
+    }
+
+    proptest! {
+        #[test]
+        fn instruction_round_trips(action in "[NSEWLRF]", val: i32) {
+            let instruction: Instruction = format!("{}{}", action, val).parse().unwrap();
+            prop_assert_eq!(instruction.to_string().parse::<Instruction>().unwrap(), instruction);
+            prop_assert_eq!(instruction.to_string(), format!("{}{}", action, val));
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use itertools::Itertools;
+use std::io::BufRead;
+
+use crate::{
+    generate::{Generated, Generator, Rng},
+    math, parse, Error, ParseError, Solution,
+};
+
+pub struct Notes {
+    earliest: i64,
+    buses: Vec<(i64, i64)>,
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Notes;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_notes(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+}
+
+pub fn part1(notes: &Notes) -> i64 {
+    let result = notes
+        .buses
+        .iter()
+        .map(|(_, bus)| (bus, (notes.earliest / bus + 1) * bus - notes.earliest))
+        .min_by(|x, y| x.1.cmp(&y.1))
+        .unwrap();
+    result.0 * result.1
+}
+
+pub fn part2(notes: &Notes) -> i64 {
+    let departures: Vec<_> = notes
+        .buses
+        .iter()
+        .map(|&(offset, bus)| (offset, bus as u64))
+        .collect();
+    let (timestamp, _) =
+        math::crt(&departures).unwrap_or_else(|e| panic!("no such timestamp: {}", e));
+    timestamp as i64
+}
+
+pub fn read_notes<R: BufRead>(reader: R) -> Result<Notes, Error> {
+    let mut lines = parse::lines(reader);
+    let (_, line) = lines
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(Day13::DAY, "missing earliest timestamp"))??;
+    let earliest = parse::number(Day13::DAY, &line, &line).map_err(|e| e.on_line(1))?;
+
+    let (_, line) = lines
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(Day13::DAY, "missing bus IDs"))??;
+    let buses: Vec<(i64, i64)> = line
+        .split(',')
+        .enumerate()
+        .filter(|(_, bus)| *bus != "x")
+        .map(|(i, bus)| match parse::number(Day13::DAY, &line, bus)? {
+            id if id > 0 => Ok((-(i as i64), id)),
+            _ => Err(ParseError::within(
+                Day13::DAY,
+                &line,
+                bus,
+                "bus ID must be positive",
+            )),
+        })
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.on_line(2))?;
+    if buses.is_empty() {
+        return Err(ParseError::line(Day13::DAY, &line, "no buses in service")
+            .on_line(2)
+            .into());
+    }
+
+    Ok(Notes { earliest, buses })
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "buses",
+    sizes: 1..=9,
+    default_size: 9,
+    build: generate,
+};
+
+/// Buses with prime IDs, so any timestamp below their product is the answer
+/// for some offsets, and the offsets derived from a random one.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    const LIMIT: f64 = 4e18;
+    let primes: Vec<u64> = (11..1000u64)
+        .filter(|&n| (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0))
+        .collect();
+    let mut buses: Vec<u64> = Vec::new();
+    let mut product = 1;
+    while buses.len() < size {
+        let left = (size - buses.len()) as f64;
+        let bound = (LIMIT / product as f64).powf(1.0 / left) as u64;
+        let bus = *rng.choose(&primes);
+        if bus <= bound && !buses.contains(&bus) {
+            buses.push(bus);
+            product *= bus;
+        }
+    }
+
+    let timestamp = rng.range(0..=product as i64 - 1);
+    let mut schedule: Vec<String> = Vec::new();
+    for &bus in &buses {
+        let mut offset = (bus - timestamp as u64 % bus) % bus;
+        while schedule
+            .get(offset as usize)
+            .is_some_and(|slot| slot != "x")
+        {
+            offset += bus;
+        }
+        if schedule.len() <= offset as usize {
+            schedule.resize(offset as usize + 1, "x".to_owned());
+        }
+        schedule[offset as usize] = bus.to_string();
+    }
+
+    loop {
+        let earliest = rng.range(100_000..=1_000_000);
+        let waits: Vec<(i64, i64)> = buses
+            .iter()
+            .map(|&bus| bus as i64)
+            .map(|bus| (bus, (earliest / bus + 1) * bus - earliest))
+            .sorted_by_key(|&(_, wait)| wait)
+            .collect();
+        if waits.len() == 1 || waits[0].1 < waits[1].1 {
+            return Generated::new(format!("{}\n{}\n", earliest, schedule.join(",")))
+                .with_answer(1, waits[0].0 * waits[0].1)
+                .with_answer(2, timestamp);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let notes = read_notes(BufReader::new(File::open("inputs/day13/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&notes), 333);
+        assert_eq!(part2(&notes), 690123192779524);
+    }
+
+    #[test]
+    fn test_no_buses() {
+        let error = read_notes("939\nx,x\n".as_bytes()).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 13, line 2, column 1: no buses in service (`x,x`)"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn timestamp_satisfies_every_bus(
+            buses in prop::sample::subsequence(vec![2i64, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37], 1..8)
+                .prop_shuffle(),
+            gaps in prop::collection::vec(0..4usize, 8),
+        ) {
+            let line = buses
+                .iter()
+                .zip(&gaps)
+                .map(|(bus, &gap)| format!("{}{}", "x,".repeat(gap), bus))
+                .collect::<Vec<_>>()
+                .join(",");
+            let notes = read_notes(format!("939\n{}\n", line).as_bytes()).unwrap();
+            let timestamp = part2(&notes);
+
+            let product: i64 = buses.iter().product();
+            prop_assert!((0..product).contains(&timestamp));
+            for (i, bus) in line.split(',').enumerate() {
+                if let Ok(bus) = bus.parse::<i64>() {
+                    prop_assert_eq!((timestamp + i as i64) % bus, 0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{collections::HashMap, io::BufRead};
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    parse, Error, ParseError, Solution,
+};
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Vec<ProgramChunk>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_input(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+}
+
+pub fn part1(input: &[ProgramChunk]) -> u64 {
+    let mut mem: HashMap<u64, u64> = HashMap::new();
+
+    for chunk in input.iter() {
+        for instruction in chunk.instructions.iter() {
+            mem.insert(
+                instruction.0,
+                (instruction.1 & chunk.bitmask_zeroes) | chunk.bitmask_ones,
+            );
+        }
+    }
+    mem.values().sum()
+}
+
+pub fn part2(input: &[ProgramChunk]) -> u64 {
+    let mut mem: HashMap<u64, u64> = HashMap::new();
+
+    for chunk in input.iter() {
+        for instruction in chunk.instructions.iter() {
+            for variation in apply_with_variations(instruction.0, &chunk.original_bitmask) {
+                mem.insert(variation, instruction.1);
+            }
+        }
+    }
+    mem.values().sum()
+}
+
+#[derive(Default, Debug)]
+pub struct ProgramChunk {
+    original_bitmask: Vec<u8>,
+    bitmask_zeroes: u64,
+    bitmask_ones: u64,
+    instructions: Vec<(u64, u64)>,
+}
+
+fn apply_with_variations(num: u64, bitmask: &[u8]) -> Vec<u64> {
+    let num_str = format!("{:036b}", num);
+
+    let intermediary = num_str
+        .chars()
+        .zip(bitmask)
+        .map(|(ch, b)| match *b as char {
+            '0' => ch as u8,
+            '1' => b'1',
+            'X' => b'X',
+            _ => panic!("fiasco"),
+        })
+        .collect::<Vec<_>>();
+
+    bitmask_variation(&intermediary)
+}
+
+fn bitmask_variation(bitmask: &[u8]) -> Vec<u64> {
+    let mut result = Vec::new();
+    if let Some(pos) = bitmask.iter().position(|&ch| ch == b'X') {
+        let mut new_bitmask = bitmask.to_owned();
+        new_bitmask[pos] = b'0';
+        result.append(&mut bitmask_variation(&new_bitmask));
+        new_bitmask[pos] = b'1';
+        result.append(&mut bitmask_variation(&new_bitmask));
+    } else {
+        let mut bitmask_ones = 0u64;
+        for b in bitmask {
+            match *b as char {
+                '0' => {
+                    bitmask_ones <<= 1;
+                }
+                '1' => {
+                    bitmask_ones = bitmask_ones << 1 | 1;
+                }
+                _ => panic!("fiasco input"),
+            };
+        }
+        result.push(bitmask_ones);
+    };
+
+    result
+}
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Vec<ProgramChunk>, Error> {
+    let mut result = Vec::new();
+    let mut current_chunk = ProgramChunk::default();
+
+    for line in parse::lines(reader) {
+        let (num, line) = line?;
+        lazy_static! {
+            static ref MASK_RE: Regex = Regex::new(r"^mask = ([X01]{36})$").unwrap();
+            static ref ASSIGNMENT_RE: Regex = Regex::new(r"^mem\[(\d+)\] = (\d+)$").unwrap();
+        }
+
+        if let Some(caps) = MASK_RE.captures(&line) {
+            if !current_chunk.instructions.is_empty() {
+                result.push(current_chunk);
+            }
+
+            let mut bitmask_zeroes = 0u64;
+            let mut bitmask_ones = 0u64;
+            for ch in caps[1].chars() {
+                match ch {
+                    'X' => {
+                        bitmask_zeroes = bitmask_zeroes << 1 | 1;
+                        bitmask_ones <<= 1;
+                    }
+                    '0' => {
+                        bitmask_zeroes <<= 1;
+                        bitmask_ones <<= 1;
+                    }
+                    _ => {
+                        bitmask_zeroes <<= 1;
+                        bitmask_ones = bitmask_ones << 1 | 1;
+                    }
+                };
+            }
+            current_chunk = ProgramChunk {
+                bitmask_zeroes,
+                bitmask_ones,
+                original_bitmask: caps[1].chars().map(|ch| ch as u8).collect(),
+                instructions: Vec::new(),
+            }
+        } else if let Some(caps) = ASSIGNMENT_RE.captures(&line) {
+            let address = parse::number(Day14::DAY, &line, &caps[1]).map_err(|e| e.on_line(num))?;
+            let value = parse::number(Day14::DAY, &line, &caps[2]).map_err(|e| e.on_line(num))?;
+            current_chunk.instructions.push((address, value));
+        } else if !line.is_empty() {
+            return Err(ParseError::line(
+                Day14::DAY,
+                &line,
+                "expected `mask = <36 bits>` or `mem[<address>] = <value>`",
+            )
+            .on_line(num)
+            .into());
+        }
+    }
+    result.push(current_chunk);
+
+    Ok(result)
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "masks",
+    sizes: 1..=200,
+    default_size: 100,
+    build: generate,
+};
+
+/// Masks with up to nine floating bits, each followed by a few writes.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let (mut memory1, mut memory2) = (HashMap::new(), HashMap::new());
+    let mut lines = Vec::new();
+    for _ in 0..size {
+        let mut mask: Vec<char> = (0..36)
+            .map(|_| if rng.chance(50) { '0' } else { '1' })
+            .collect();
+        for _ in 0..rng.range(3..=9) {
+            let bit = rng.index(36);
+            mask[bit] = 'X';
+        }
+        let floating: Vec<usize> = (0..36).filter(|&bit| mask[35 - bit] == 'X').collect();
+        let bits = |value: char| {
+            (0..36)
+                .filter(|&bit| mask[35 - bit] == value)
+                .fold(0u64, |bits, bit| bits | 1 << bit)
+        };
+        let (ones, zeroes) = (bits('1'), bits('0'));
+        lines.push(format!("mask = {}", mask.iter().collect::<String>()));
+
+        for _ in 0..rng.range(1..=6) {
+            let address = rng.range(0..=65535) as u64;
+            let value = rng.range(0..=1 << 30) as u64;
+            lines.push(format!("mem[{}] = {}", address, value));
+            memory1.insert(address, value & !zeroes | ones);
+            for variation in 0..1u64 << floating.len() {
+                let address = floating
+                    .iter()
+                    .enumerate()
+                    .fold(address | ones, |address, (i, &bit)| {
+                        address & !(1 << bit) | (variation >> i & 1) << bit
+                    });
+                memory2.insert(address, value);
+            }
+        }
+    }
+    Generated::new(generate::lines(lines))
+        .with_answer(1, memory1.values().sum::<u64>())
+        .with_answer(2, memory2.values().sum::<u64>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_example1() {
+        let input = read_input(BufReader::new(
+            File::open("inputs/day14/example.txt").unwrap(),
+        ))
+        .unwrap();
+        assert_eq!(part1(&input), 165);
+    }
+
+    #[test]
+    fn test_example2() {
+        let input = read_input(BufReader::new(
+            File::open("inputs/day14/example2.txt").unwrap(),
+        ))
+        .unwrap();
+        assert_eq!(part2(&input), 208);
+    }
+
+    #[test]
+    fn test_solution() {
+        let input = read_input(BufReader::new(File::open("inputs/day14/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&input), 5055782549997);
+        assert_eq!(part2(&input), 4795970362286);
+    }
+}
@@ -0,0 +1,138 @@
+use itertools::Itertools;
+use std::{collections::HashMap, io::BufRead};
+
+use crate::{
+    generate::{Generated, Generator, Rng},
+    params, parse, Error, Param, Params, ParseError, Solution,
+};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_numbers(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        Self::part1_with(input, &Params::default())
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        Self::part2_with(input, &Params::default())
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+
+    const PARAMS: &'static [Param] = &[PART1_TURN, PART2_TURN];
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Self::Answer1 {
+        find_nth_result(input, PART1_TURN.get(params))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Self::Answer2 {
+        find_nth_result(input, PART2_TURN.get(params))
+    }
+}
+
+pub const PART1_TURN: Param = Param {
+    name: "part1-turn",
+    default: "2020",
+    help: "Turn whose number part 1 asks for",
+    // Every turn may bring up a number not spoken before, to be remembered.
+    check: params::between::<u32, 1, 100_000_000>,
+};
+
+pub const PART2_TURN: Param = Param {
+    name: "part2-turn",
+    default: "30000000",
+    help: "Turn whose number part 2 asks for",
+    // Every turn may bring up a number not spoken before, to be remembered.
+    check: params::between::<u32, 1, 100_000_000>,
+};
+
+/// The number spoken on turn `end_turn`, counting from 1.
+pub fn find_nth_result(numbers: &[u32], end_turn: u32) -> u32 {
+    if let Some(&number) = numbers.get(end_turn as usize - 1) {
+        return number;
+    }
+
+    let mut num_occurences: HashMap<u32, u32> = HashMap::new();
+    for (i, num) in numbers.iter().enumerate() {
+        num_occurences.insert(*num, i as u32 + 1);
+    }
+
+    let mut last_num = 0;
+    let mut turn = numbers.len() as u32 + 1;
+
+    while turn != end_turn {
+        if let Some(last_occurence) = num_occurences.get_mut(&last_num) {
+            last_num = turn - *last_occurence;
+            *last_occurence = turn;
+        } else {
+            num_occurences.insert(last_num, turn);
+            last_num = 0;
+        }
+
+        turn += 1;
+    }
+    last_num
+}
+
+pub fn read_numbers<R: BufRead>(reader: R) -> Result<Vec<u32>, Error> {
+    let (_, line) = parse::lines(reader)
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(Day15::DAY, "missing starting numbers"))??;
+    let numbers = line
+        .split(',')
+        .map(|num| parse::number(Day15::DAY, &line, num))
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.on_line(1))?;
+    Ok(numbers)
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "starting numbers",
+    sizes: 2..=10,
+    default_size: 6,
+    build: generate,
+};
+
+/// Distinct starting numbers, the last one must not be repeated for the
+/// game to start with a 0.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let mut numbers: Vec<u32> = (0..20).collect();
+    rng.shuffle(&mut numbers);
+    Generated::new(format!("{}\n", numbers[..size].iter().join(",")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let numbers =
+            read_numbers(BufReader::new(File::open("inputs/day15/1.txt").unwrap())).unwrap();
+        assert_eq!(find_nth_result(&numbers, 2020), 959);
+        assert_eq!(find_nth_result(&numbers, 30000000), 116590);
+        assert_eq!(find_nth_result(&numbers, 2), numbers[1]);
+    }
+
+    #[test]
+    fn test_params() {
+        let params = |value: &str| Params::new(Day15::PARAMS, &[value.to_owned()]);
+        assert!(params("part2-turn=100000000").is_ok());
+        assert_eq!(
+            params("part2-turn=100000001").unwrap_err(),
+            "invalid part2-turn `100000001`: expected a u32 from 1 to 100000000"
+        );
+        assert!(params("part1-turn=0").is_err());
+    }
+}
@@ -0,0 +1,317 @@
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{collections::HashMap, io::BufRead};
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    parse, Error, ParseError, Solution,
+};
+
+#[derive(Clone, Debug)]
+pub struct Rule {
+    name: String,
+    first: (u32, u32),
+    second: (u32, u32),
+}
+
+impl Rule {
+    fn is_valid(&self, num: u32) -> bool {
+        num >= self.first.0 && num <= self.first.1 || num >= self.second.0 && num <= self.second.1
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Input {
+    rules: Vec<Rule>,
+    ticket: Vec<u32>,
+    nearby_tickets: Vec<Vec<u32>>,
+}
+
+impl Input {
+    fn discard_invalid(&mut self) -> u32 {
+        let mut result = 0;
+        let rules = &self.rules;
+        self.nearby_tickets.retain(|ticket| {
+            let mut ticket_valid = true;
+            for num in ticket {
+                let mut valid = false;
+                for rule in rules.iter() {
+                    if rule.is_valid(*num) {
+                        valid = true;
+                        break;
+                    }
+                }
+
+                if !valid {
+                    ticket_valid = false;
+                    result += num;
+                }
+            }
+            ticket_valid
+        });
+        result
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = Input;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_input(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+}
+
+pub fn part1(input: &Input) -> u32 {
+    input.clone().discard_invalid()
+}
+pub fn part2(input: &Input) -> u64 {
+    let mut input = input.clone();
+    input.discard_invalid();
+
+    let mut all_tickets = input.nearby_tickets.clone();
+    all_tickets.push(input.ticket.clone());
+
+    let transposed: Vec<Vec<_>> = (0..all_tickets[0].len())
+        .map(|i| all_tickets.iter().map(|inner| inner[i]).collect::<Vec<_>>())
+        .collect();
+
+    let mut valid_positions: HashMap<String, Vec<u32>> = HashMap::new();
+    for (pos, col) in transposed.iter().enumerate() {
+        for rule in input.rules.iter() {
+            if col.iter().all(|num| rule.is_valid(*num)) {
+                let entry = valid_positions.entry(rule.name.clone()).or_default();
+                entry.push(pos as u32);
+            }
+        }
+    }
+
+    while valid_positions.values().any(|v| v.len() != 1) {
+        let set_positions: Vec<u32> = valid_positions
+            .values()
+            .filter(|v| v.len() == 1)
+            .map(|v| v[0])
+            .collect();
+
+        for (_, v) in valid_positions.iter_mut().filter(|(_, v)| v.len() != 1) {
+            v.retain(|v| !set_positions.contains(v));
+        }
+    }
+
+    valid_positions
+        .iter()
+        .filter(|(k, _)| k.starts_with("departure"))
+        .map(|(_, v)| input.ticket[v[0] as usize] as u64)
+        .product()
+}
+
+fn read_ticket(line: &str) -> Result<Vec<u32>, ParseError> {
+    line.split(',')
+        .map(|v| parse::number(Day16::DAY, line, v))
+        .collect()
+}
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, Error> {
+    let mut rules: Vec<Rule> = Vec::new();
+    let mut ticket: Vec<u32> = Vec::new();
+    let mut nearby_tickets: Vec<Vec<u32>> = Vec::new();
+
+    let mut section = 0;
+    for line in parse::lines(reader) {
+        let (num, line) = line?;
+        if line.is_empty() {
+            continue;
+        }
+
+        if line == "your ticket:" {
+            section = 1;
+            continue;
+        }
+
+        if line == "nearby tickets:" {
+            section = 2;
+            continue;
+        }
+
+        if section == 0 {
+            lazy_static! {
+                static ref RULE_RE: Regex =
+                    Regex::new(r"^(.+): (\d+)-(\d+) or (\d+)-(\d+)$").unwrap();
+            }
+
+            let caps = RULE_RE.captures(&line).ok_or_else(|| {
+                ParseError::line(Day16::DAY, &line, "expected `<field>: <a>-<b> or <c>-<d>`")
+                    .on_line(num)
+            })?;
+            let bound = |i| parse::number(Day16::DAY, &line, &caps[i]).map_err(|e| e.on_line(num));
+            rules.push(Rule {
+                name: caps[1].to_owned(),
+                first: (bound(2)?, bound(3)?),
+                second: (bound(4)?, bound(5)?),
+            })
+        } else {
+            let values = read_ticket(&line).map_err(|e| e.on_line(num))?;
+            if section == 1 {
+                ticket = values;
+            } else if values.len() != ticket.len() {
+                return Err(ParseError::line(
+                    Day16::DAY,
+                    &line,
+                    format!("expected {} values like your ticket", ticket.len()),
+                )
+                .on_line(num)
+                .into());
+            } else {
+                nearby_tickets.push(values);
+            }
+        }
+    }
+
+    if ticket.is_empty() {
+        return Err(ParseError::end_of_input(Day16::DAY, "missing your ticket").into());
+    }
+
+    Ok(Input {
+        rules,
+        ticket,
+        nearby_tickets,
+    })
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "nearby tickets",
+    sizes: 1..=500,
+    default_size: 240,
+    build: generate,
+};
+
+/// The fields, with `departure` ones first.
+const FIELDS: [&str; 20] = [
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+/// Values come in tiers 50 apart, a field of rank `k` takes values of the
+/// first `k + 1` tiers. A column with some value of tier `k` then fits the
+/// fields of rank `k` and up only, so the fields can be told apart one rank
+/// after another, as in the puzzle.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let tier = |rng: &mut Rng, tier: usize| 50 + 50 * tier as u32 + rng.range(0..=39) as u32;
+    let mut ranks: Vec<usize> = (0..FIELDS.len()).collect();
+    rng.shuffle(&mut ranks);
+    let mut positions: Vec<usize> = (0..FIELDS.len()).collect();
+    rng.shuffle(&mut positions);
+    // The rank of the field at every position.
+    let mut columns = vec![0; FIELDS.len()];
+    for (field, &rank) in ranks.iter().enumerate() {
+        columns[positions[field]] = rank;
+    }
+
+    let mut lines: Vec<String> = FIELDS
+        .iter()
+        .zip(&ranks)
+        .map(|(name, &rank)| {
+            let split = if rank == 0 {
+                69
+            } else {
+                50 * rng.range(0..=rank as i64 - 1) as u32 + 89
+            };
+            let second = if rank == 0 { 70 } else { split + 11 };
+            format!(
+                "{}: 50-{} or {}-{}",
+                name,
+                split,
+                second,
+                50 * rank as u32 + 89
+            )
+        })
+        .collect();
+
+    let ticket: Vec<u32> = columns.iter().map(|&rank| tier(rng, rank)).collect();
+    let departures = FIELDS
+        .iter()
+        .zip(&positions)
+        .filter(|(name, _)| name.starts_with("departure"))
+        .map(|(_, &position)| u64::from(ticket[position]))
+        .product::<u64>();
+    lines.extend(vec![
+        String::new(),
+        "your ticket:".to_owned(),
+        ticket.iter().join(","),
+        String::new(),
+        "nearby tickets:".to_owned(),
+    ]);
+
+    let mut error_rate = 0;
+    for _ in 0..size {
+        let mut nearby: Vec<u32> = columns
+            .iter()
+            .map(|&rank| {
+                let below = rng.range(0..=rank as i64) as usize;
+                tier(rng, below)
+            })
+            .collect();
+        if rng.chance(25) {
+            let invalid = if rng.chance(50) {
+                rng.range(1..=49)
+            } else {
+                rng.range(1050..=1099)
+            } as u32;
+            let position = rng.index(nearby.len());
+            nearby[position] = invalid;
+            error_rate += invalid;
+        }
+        lines.push(nearby.iter().join(","));
+    }
+
+    Generated::new(generate::lines(lines))
+        .with_answer(1, error_rate)
+        .with_answer(2, departures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let input = read_input(BufReader::new(File::open("inputs/day16/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&input), 28873);
+        assert_eq!(part2(&input), 2587271823407);
+    }
+}
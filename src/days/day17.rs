@@ -0,0 +1,234 @@
+use std::{collections::HashSet, io::BufRead, iter};
+
+use crate::{
+    automaton::{Automaton, Lattice, Rule},
+    generate::{Generated, Generator, Rng},
+    grid::Grid,
+    params,
+    render::{self, Cell, Frame, Rgb},
+    Error, Param, Params, Solution,
+};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input = Game;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_game(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        Self::part1_with(input, &Params::default())
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        Self::part2_with(input, &Params::default())
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+
+    const PARAMS: &'static [Param] = &[CYCLES];
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Self::Answer1 {
+        part1(input, CYCLES.get(params))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Self::Answer2 {
+        part2(input, CYCLES.get(params))
+    }
+}
+
+pub const CYCLES: Param = Param {
+    name: "cycles",
+    default: "6",
+    help: "Cycles of the boot process",
+    // The active cubes spread by a cube in every direction each cycle, in
+    // four dimensions for part 2.
+    check: params::between::<usize, 0, 20>,
+};
+
+/// The initial slice of the pocket dimension, as `(x, y)` of active cubes.
+#[derive(Clone, Debug)]
+pub struct Game {
+    active: Vec<(i32, i32)>,
+}
+
+impl Game {
+    /// The pocket dimension with `N` dimensions, the slice lying at 0 in all
+    /// but the first two.
+    pub fn automaton<const N: usize>(&self) -> Automaton<Lattice<N>> {
+        let active = self.active.iter().map(|&(x, y)| {
+            let mut cube = [0; N];
+            cube[0] = x;
+            cube[1] = y;
+            cube
+        });
+        Automaton::new(Lattice::new(), Rule::new(vec![3], vec![2, 3]), active)
+    }
+
+    fn boot<const N: usize>(&self, cycles: usize) -> usize {
+        let mut automaton = self.automaton::<N>();
+        automaton.run(cycles);
+        automaton.population()
+    }
+}
+
+/// Every slice of the cubes within `bounds` along the axes after `x` and `y`,
+/// `z` left to right and `w` top to bottom.
+pub fn frame<const N: usize>(active: &HashSet<[i32; N]>, bounds: &[(i32, i32); N]) -> Frame {
+    let ((min_x, max_x), (min_y, max_y)) = (bounds[0], bounds[1]);
+    let (width, height) = ((max_x - min_x + 1) as usize, (max_y - min_y + 1) as usize);
+
+    let slices = bounds[2..]
+        .iter()
+        .rev()
+        .fold(vec![Vec::new()], |slices, &(min, max)| {
+            slices
+                .iter()
+                .flat_map(|outer| {
+                    (min..=max).map(move |coordinate| {
+                        let mut slice = outer.clone();
+                        slice.push(coordinate);
+                        slice
+                    })
+                })
+                .collect()
+        });
+    let panels: Vec<_> = slices
+        .into_iter()
+        .map(|mut slice| {
+            slice.reverse();
+            let caption = slice
+                .iter()
+                .zip(["z", "w"].iter().chain(iter::repeat(&"?")))
+                .map(|(coordinate, axis)| format!("{}={}", axis, coordinate))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let panel = Grid::from_fn(width, height, |(x, y)| {
+                let mut cube = [0; N];
+                cube[0] = x as i32 + min_x;
+                cube[1] = y as i32 + min_y;
+                cube[2..].copy_from_slice(&slice);
+                if active.contains(&cube) {
+                    Cell::new('#', Rgb(80, 160, 255))
+                } else {
+                    Cell::new('.', Rgb(20, 20, 50))
+                }
+            });
+            (caption, panel)
+        })
+        .collect();
+
+    let columns = bounds
+        .get(2)
+        .map_or(1, |(min, max)| (max - min + 1) as usize);
+    render::mosaic(&panels, columns)
+}
+
+/// Every cycle of the boot process, in three dimensions for part 1 and four
+/// for part 2.
+pub fn animation<'a>(
+    game: &'a Game,
+    part: u8,
+    params: &Params,
+) -> Box<dyn Iterator<Item = Frame> + 'a> {
+    let cycles = CYCLES.get(params);
+    match part {
+        1 => boot_animation::<3>(game, cycles),
+        _ => boot_animation::<4>(game, cycles),
+    }
+}
+
+fn boot_animation<const N: usize>(
+    game: &Game,
+    cycles: usize,
+) -> Box<dyn Iterator<Item = Frame> + '_> {
+    // The bounds of all cycles together keep the slices in place.
+    let mut bounds = [(0, 0); N];
+    let mut automaton = game.automaton::<N>();
+    for cycle in 0..=cycles {
+        for cube in automaton.live() {
+            for (axis, &coordinate) in cube.iter().enumerate() {
+                let (min, max) = &mut bounds[axis];
+                *min = (*min).min(coordinate);
+                *max = (*max).max(coordinate);
+            }
+        }
+        if cycle < cycles {
+            automaton.step();
+        }
+    }
+
+    let automaton = game.automaton::<N>();
+    Box::new(automaton.animate(Some(cycles), move |automaton| {
+        frame(&automaton.live().collect(), &bounds)
+    }))
+}
+
+pub fn part1(game: &Game, cycles: usize) -> usize {
+    game.boot::<3>(cycles)
+}
+
+pub fn part2(game: &Game, cycles: usize) -> usize {
+    game.boot::<4>(cycles)
+}
+
+pub fn read_game<R: BufRead>(reader: R) -> Result<Game, Error> {
+    let slice = Grid::parse(
+        Day17::DAY,
+        reader,
+        |ch| match ch {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        },
+        "expected `.` or `#`",
+    )?;
+    let active = slice
+        .iter()
+        .filter(|(_, &active)| active)
+        .map(|((x, y), _)| (x as i32, y as i32))
+        .collect();
+
+    Ok(Game { active })
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "rows",
+    sizes: 1..=12,
+    default_size: 8,
+    build: generate,
+};
+
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let slice = Grid::from_fn(size, size, |_| if rng.chance(50) { '#' } else { '.' });
+    Generated::new(format!("{}\n", slice))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let game = read_game(BufReader::new(File::open("inputs/day17/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&game, 6), 401);
+        assert_eq!(part2(&game, 6), 2224);
+    }
+
+    #[test]
+    fn test_params() {
+        let params = |value: &str| Params::new(Day17::PARAMS, &[value.to_owned()]);
+        assert!(params("cycles=20").is_ok());
+        assert_eq!(
+            params("cycles=21").unwrap_err(),
+            "invalid cycles `21`: expected a usize from 0 to 20"
+        );
+    }
+}
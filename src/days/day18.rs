@@ -0,0 +1,342 @@
+use std::{io::BufRead, str::FromStr};
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    parse, Answers, Error, Params, ParseError, Solution,
+};
+
+pub struct Equation {
+    raw: String,
+}
+
+impl Equation {
+    fn calculate(&self, plus_precedence: bool) -> i64 {
+        // Shunting-Yard algorithm
+        // https://en.wikipedia.org/wiki/Shunting-yard_algorithm
+        let mut output = Vec::new();
+        let mut operators = Vec::new();
+        for ch in self.raw.chars() {
+            if ch.is_ascii_digit() {
+                output.push(ch);
+            } else if Equation::is_operator(ch) {
+                if !plus_precedence {
+                    while let Some(operator) = operators.pop() {
+                        if operator == '(' {
+                            operators.push('(');
+                            break;
+                        } else {
+                            output.push(operator);
+                        }
+                    }
+                } else if let Some(operator) = operators.pop() {
+                    if operator == '(' {
+                        operators.push('(');
+                    } else if operator == '+' {
+                        output.push('+');
+                    } else {
+                        operators.push(operator);
+                    }
+                }
+                operators.push(ch);
+            } else if ch == '(' {
+                operators.push(ch);
+            } else if ch == ')' {
+                while let Some(operator) = operators.pop() {
+                    if operator == '(' {
+                        break;
+                    } else {
+                        output.push(operator);
+                    }
+                }
+            }
+        }
+
+        while let Some(operator) = operators.pop() {
+            output.push(operator);
+        }
+
+        // Evaluation of Reverse Polish Notation
+        let mut stack: Vec<i64> = Vec::new();
+        for &token in output.iter() {
+            match token.to_string().parse::<i64>() {
+                Ok(num) => {
+                    stack.push(num);
+                }
+                Err(_) => match token {
+                    '+' => {
+                        let a = stack.pop().unwrap();
+                        let b = stack.pop().unwrap();
+                        stack.push(a + b);
+                    }
+                    '*' => {
+                        let a = stack.pop().unwrap();
+                        let b = stack.pop().unwrap();
+                        stack.push(a * b);
+                    }
+                    _ => panic!("Unknown operator!"),
+                },
+            }
+        }
+        stack.pop().unwrap_or(0)
+    }
+
+    /// Checks that `line` only has single digit numbers, operators and
+    /// balanced parentheses in the right order, so it can be calculated.
+    fn validate(line: &str) -> Result<(), ParseError> {
+        let error = |i: usize, ch: char, reason: &str| {
+            ParseError::within(Day18::DAY, line, &line[i..i + ch.len_utf8()], reason)
+        };
+
+        let mut expect_operand = true;
+        let mut depth = 0;
+        let mut last = (0, ' ');
+        for (i, ch) in line.char_indices().filter(|(_, ch)| *ch != ' ') {
+            last = (i, ch);
+            if expect_operand {
+                match ch {
+                    '(' => depth += 1,
+                    _ if ch.is_ascii_digit() => expect_operand = false,
+                    _ => return Err(error(i, ch, "expected a digit or `(`")),
+                }
+            } else {
+                match ch {
+                    ')' if depth > 0 => depth -= 1,
+                    ')' => return Err(error(i, ch, "unbalanced `)`")),
+                    _ if Equation::is_operator(ch) => expect_operand = true,
+                    _ => return Err(error(i, ch, "expected an operator or `)`")),
+                }
+            }
+        }
+
+        if expect_operand {
+            Err(error(last.0, last.1, "incomplete expression"))
+        } else if depth > 0 {
+            Err(ParseError::line(Day18::DAY, line, "unbalanced `(`"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn is_operator(ch: char) -> bool {
+        ch == '+' || ch == '*'
+    }
+}
+
+impl FromStr for Equation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Equation::validate(s)?;
+        Ok(Equation {
+            raw: s.replace(' ', ""),
+        })
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input = Vec<Equation>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_equations(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+
+    fn stream<R: BufRead>(reader: R, _params: &Params) -> Option<Result<Answers<Self>, Error>> {
+        Some(stream(reader))
+    }
+}
+
+pub fn part1(equations: &[Equation]) -> i64 {
+    equations
+        .iter()
+        .fold(0, |acc, eq| acc + eq.calculate(false))
+}
+
+pub fn part2(equations: &[Equation]) -> i64 {
+    equations.iter().fold(0, |acc, eq| acc + eq.calculate(true))
+}
+
+/// Sums of both parts, calculating every equation as it is read.
+pub fn stream<R: BufRead>(reader: R) -> Result<(i64, i64), Error> {
+    let mut sums = (0, 0);
+    for equation in parse::records(reader, Equation::from_str) {
+        let equation = equation?;
+        sums.0 += equation.calculate(false);
+        sums.1 += equation.calculate(true);
+    }
+    Ok(sums)
+}
+
+pub fn read_equations<R: BufRead>(reader: R) -> Result<Vec<Equation>, Error> {
+    parse::records(reader, Equation::from_str).collect()
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "expressions",
+    sizes: 1..=1000,
+    default_size: 380,
+    build: generate,
+};
+
+/// An expression of up to `depth` nested parentheses, with its value when
+/// evaluated left to right and when additions go first, `None` on overflow.
+fn expression(rng: &mut Rng, depth: u32) -> (String, Option<(i64, i64)>) {
+    let mut text = String::new();
+    // The value so far left to right, and the finished product and the
+    // running sum with additions first.
+    let mut value: Option<(i64, i64, i64)> = Some((0, 1, 0));
+    for i in 0..rng.range(2..=6) {
+        let add = i == 0 || rng.chance(50);
+        if i > 0 {
+            text += if add { " + " } else { " * " };
+        }
+        let (operand, operand_value) = if depth > 0 && rng.chance(25) {
+            let (inner, inner_value) = expression(rng, depth - 1);
+            (format!("({})", inner), inner_value)
+        } else {
+            let digit = rng.range(1..=9);
+            (digit.to_string(), Some((digit, digit)))
+        };
+        text += &operand;
+        value = value.zip(operand_value).and_then(
+            |((flat, product, sum), (operand_flat, operand_precedence))| {
+                let flat = if add {
+                    flat.checked_add(operand_flat)?
+                } else {
+                    flat.checked_mul(operand_flat)?
+                };
+                if add {
+                    Some((flat, product, sum.checked_add(operand_precedence)?))
+                } else {
+                    Some((flat, product.checked_mul(sum)?, operand_precedence))
+                }
+            },
+        );
+    }
+    let value = value.and_then(|(flat, product, sum)| Some((flat, product.checked_mul(sum)?)));
+    (text, value)
+}
+
+/// Expressions small enough for their sums to fit an `i64`.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    const LIMIT: i64 = 1_000_000_000_000;
+    let (mut sum1, mut sum2) = (0, 0);
+    let mut lines = Vec::new();
+    while lines.len() < size {
+        let (text, value) = expression(rng, 2);
+        if let Some((flat, precedence)) = value.filter(|&(_, precedence)| precedence <= LIMIT) {
+            sum1 += flat;
+            sum2 += precedence;
+            lines.push(text);
+        }
+    }
+    Generated::new(generate::lines(lines))
+        .with_answer(1, sum1)
+        .with_answer(2, sum2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let equations =
+            read_equations(BufReader::new(File::open("inputs/day18/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&equations), 4940631886147);
+        assert_eq!(part2(&equations), 283582817678281);
+    }
+
+    /// Terms joined by random operators, e.g. `2 * (3 + 4) + 5`.
+    fn expression() -> BoxedStrategy<String> {
+        let digit = (1..10u32).prop_map(|digit| digit.to_string());
+        let term = digit.prop_recursive(3, 16, 4, |term| {
+            chain(term).prop_map(|chain| format!("({})", chain))
+        });
+        chain(term.boxed())
+    }
+
+    fn chain(term: BoxedStrategy<String>) -> BoxedStrategy<String> {
+        (
+            term.clone(),
+            prop::collection::vec((any::<bool>(), term), 0..4),
+        )
+            .prop_map(|(first, rest)| {
+                rest.into_iter().fold(first, |chain, (plus, term)| {
+                    format!("{} {} {}", chain, if plus { '+' } else { '*' }, term)
+                })
+            })
+            .boxed()
+    }
+
+    /// Evaluates `expression` by recursive descent, `None` if it overflows.
+    fn reference(expression: &str, plus_first: bool) -> Option<i64> {
+        fn term(tokens: &[char], pos: &mut usize, plus_first: bool) -> Option<i64> {
+            let token = tokens[*pos];
+            *pos += 1;
+            if token == '(' {
+                let value = product(tokens, pos, plus_first);
+                *pos += 1;
+                value
+            } else {
+                token.to_digit(10).map(i64::from)
+            }
+        }
+
+        fn sum(tokens: &[char], pos: &mut usize, plus_first: bool) -> Option<i64> {
+            let mut value = term(tokens, pos, plus_first)?;
+            while tokens.get(*pos) == Some(&'+') {
+                *pos += 1;
+                value = value.checked_add(term(tokens, pos, plus_first)?)?;
+            }
+            Some(value)
+        }
+
+        fn product(tokens: &[char], pos: &mut usize, plus_first: bool) -> Option<i64> {
+            let operand = if plus_first { sum } else { term };
+            let mut value = operand(tokens, pos, plus_first)?;
+            while let Some(&operator) = tokens.get(*pos).filter(|&&token| token != ')') {
+                *pos += 1;
+                let rhs = operand(tokens, pos, plus_first)?;
+                value = match operator {
+                    '+' => value.checked_add(rhs)?,
+                    _ => value.checked_mul(rhs)?,
+                };
+            }
+            Some(value)
+        }
+
+        let tokens: Vec<char> = expression.chars().filter(|&ch| ch != ' ').collect();
+        product(&tokens, &mut 0, plus_first)
+    }
+
+    proptest! {
+        #[test]
+        fn equations_match_reference(expression in expression()) {
+            let equations = read_equations(expression.as_bytes()).unwrap();
+            if let Some(expected) = reference(&expression, false) {
+                prop_assert_eq!(part1(&equations), expected);
+            }
+            if let Some(expected) = reference(&expression, true) {
+                prop_assert_eq!(part2(&equations), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{collections::HashMap, io::BufRead};
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    parse, Error, ParseError, Solution,
+};
+
+#[derive(Clone)]
+pub enum Rule {
+    CHAR(char),
+    SINGLE(Vec<u32>),
+    OR((Vec<u32>, Vec<u32>)),
+}
+
+#[derive(Default, Clone)]
+pub struct Rules {
+    rules: HashMap<u32, Rule>,
+}
+
+impl Rules {
+    pub fn add_from_str(&mut self, s: &str) -> Result<(), ParseError> {
+        lazy_static! {
+            static ref OR_RE: Regex = Regex::new(r"^(\d+): ([\w ]+)\|([\w ]+)$").unwrap();
+            static ref SINGLE_RE: Regex = Regex::new(r"^(\d+): ([\w ]+)$").unwrap();
+            static ref CHAR_RE: Regex = Regex::new("^(\\d+): \"(\\w)\"$").unwrap();
+        }
+        let subrules = |list: &str| {
+            let subrules = list
+                .split_whitespace()
+                .map(|id| parse::number(Day19::DAY, s, id))
+                .collect::<Result<Vec<u32>, _>>()?;
+            match subrules.is_empty() {
+                true => Err(ParseError::within(Day19::DAY, s, list, "expected rule ids")),
+                false => Ok(subrules),
+            }
+        };
+
+        if let Some(caps) = OR_RE.captures(s) {
+            let id: u32 = parse::number(Day19::DAY, s, &caps[1])?;
+            let first = subrules(&caps[2])?;
+            let second = subrules(&caps[3])?;
+
+            self.rules.insert(id, Rule::OR((first, second)));
+        } else if let Some(caps) = SINGLE_RE.captures(s) {
+            let id: u32 = parse::number(Day19::DAY, s, &caps[1])?;
+            let first = subrules(&caps[2])?;
+
+            self.rules.insert(id, Rule::SINGLE(first));
+        } else if let Some(caps) = CHAR_RE.captures(s) {
+            let id: u32 = parse::number(Day19::DAY, s, &caps[1])?;
+            let ch = caps[2].chars().next().unwrap_or_default();
+
+            self.rules.insert(id, Rule::CHAR(ch));
+        } else {
+            return Err(ParseError::line(
+                Day19::DAY,
+                s,
+                "expected `<id>: <ids> | <ids>`, `<id>: <ids>` or `<id>: \"<char>\"`",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that rule 0 and every rule referred to are defined.
+    fn check_references(&self) -> Result<(), ParseError> {
+        if !self.rules.contains_key(&0) {
+            return Err(ParseError::end_of_input(Day19::DAY, "missing rule 0"));
+        }
+
+        for (id, rule) in self.rules.iter() {
+            if let Some(missing) = rule
+                .alternatives()
+                .into_iter()
+                .flatten()
+                .find(|subrule| !self.rules.contains_key(subrule))
+            {
+                return Err(ParseError::end_of_input(
+                    Day19::DAY,
+                    format!("rule {} refers to missing rule {}", id, missing),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that no rule refers back to itself, which would never finish
+    /// matching. Neither may rules 42 and 31 lead to rules 8 and 11 without
+    /// matching a letter first, as the loops of part 2 would then never
+    /// finish either.
+    fn check_cycles(&self) -> Result<(), ParseError> {
+        let mut ids: Vec<u32> = self.rules.keys().copied().collect();
+        ids.sort_unstable();
+        let cycle = parse::find_cycle(ids.iter().copied(), |id| {
+            self.rules[id].alternatives().into_iter().flatten().copied()
+        });
+        if let Some(id) = cycle {
+            return Err(ParseError::end_of_input(
+                Day19::DAY,
+                format!("rule {} refers back to itself", id),
+            ));
+        }
+
+        let looped = self.looped();
+        let cycle = parse::find_cycle(ids, |id| {
+            let alternatives = looped.rules[id].alternatives();
+            alternatives.into_iter().map(|subrules| subrules[0])
+        });
+        if let Some(id) = cycle {
+            return Err(ParseError::end_of_input(
+                Day19::DAY,
+                format!("rule {} refers back to itself with the loops of part 2", id),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The rules of part 2, with rules 8 and 11 looping.
+    fn looped(&self) -> Rules {
+        let mut looped = self.clone();
+
+        if let Some(rule) = looped.rules.get_mut(&8) {
+            *rule = Rule::OR((vec![42], vec![42, 8]));
+        }
+
+        if let Some(rule) = looped.rules.get_mut(&11) {
+            *rule = Rule::OR((vec![42, 31], vec![42, 11, 31]));
+        }
+        looped
+    }
+}
+
+impl Rule {
+    /// The sequences of rules this rule matches one of, none for a letter.
+    fn alternatives(&self) -> Vec<&[u32]> {
+        match self {
+            Rule::CHAR(_) => Vec::new(),
+            Rule::SINGLE(subrules) => vec![subrules],
+            Rule::OR((first, second)) => vec![first, second],
+        }
+    }
+}
+
+pub struct Message {
+    message: String,
+}
+
+impl Message {
+    pub fn validate(&self, rules: &Rules) -> bool {
+        Message::validate_chunk(&self.message, rules, 0).contains(&Some(""))
+    }
+
+    fn validate_chunk<'a>(chunk: &'a str, rules: &Rules, rule_id: u32) -> Vec<Option<&'a str>> {
+        match rules.rules[&rule_id] {
+            Rule::CHAR(c) if chunk.starts_with(c) => vec![Some(&chunk[c.len_utf8()..])],
+            Rule::CHAR(_) => vec![None],
+            Rule::SINGLE(ref subrules) if subrules.first() == Some(&rule_id) => vec![None],
+            Rule::SINGLE(ref subrules) => {
+                subrules.iter().fold(vec![Some(chunk)], |m, &subrule_id| {
+                    m.iter()
+                        .flat_map(|chunk| match chunk {
+                            Some(chunk) if !chunk.is_empty() => {
+                                Message::validate_chunk(chunk, rules, subrule_id)
+                            }
+                            _ => vec![None],
+                        })
+                        .collect()
+                })
+            }
+            Rule::OR((ref subrules1, ref subrules2)) => [subrules1, subrules2]
+                .iter()
+                .flat_map(|subrules| {
+                    subrules
+                        .iter()
+                        .fold(vec![Some(chunk)], |chunk, &subrule_id| {
+                            chunk
+                                .iter()
+                                .flat_map(|chunk| match chunk {
+                                    Some(chunk) if !chunk.is_empty() => {
+                                        Message::validate_chunk(chunk, rules, subrule_id)
+                                    }
+                                    _ => vec![None],
+                                })
+                                .collect()
+                        })
+                })
+                .collect::<Vec<_>>(),
+        }
+    }
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Input = (Rules, Vec<Message>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_input(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(&input.0, &input.1)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(&input.0, &input.1)
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+}
+
+pub fn part1(rules: &Rules, messages: &[Message]) -> usize {
+    messages
+        .iter()
+        .filter(|message| message.validate(rules))
+        .count()
+}
+pub fn part2(rules: &Rules, messages: &[Message]) -> usize {
+    let modified_rules = rules.looped();
+
+    messages
+        .iter()
+        .filter(|message| message.validate(&modified_rules))
+        .count()
+}
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<(Rules, Vec<Message>), Error> {
+    let mut section = 0;
+    let mut rules = Rules::default();
+    let mut messages = Vec::new();
+
+    for line in parse::lines(reader) {
+        let (num, line) = line?;
+        if section == 0 {
+            if line.is_empty() {
+                section += 1;
+                continue;
+            }
+            rules.add_from_str(&line).map_err(|e| e.on_line(num))?;
+        } else {
+            messages.push(Message { message: line });
+        }
+    }
+    rules.check_references()?;
+    rules.check_cycles()?;
+
+    Ok((rules, messages))
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "messages",
+    sizes: 1..=1000,
+    default_size: 470,
+    build: generate,
+};
+
+/// Letters of the messages rules 42 and 31 match.
+const BLOCK: usize = 5;
+
+/// Adds a rule `id` matching exactly the `words`, all of the same length,
+/// letter by letter.
+fn add_rule(
+    rules: &mut Vec<String>,
+    ids: &mut impl Iterator<Item = u32>,
+    letters: (u32, u32),
+    id: u32,
+    words: &[String],
+) {
+    let mut alternatives = Vec::new();
+    for (letter, letter_id) in [('a', letters.0), ('b', letters.1)] {
+        let rest: Vec<String> = words
+            .iter()
+            .filter_map(|word| word.strip_prefix(letter))
+            .map(str::to_owned)
+            .collect();
+        if rest.is_empty() {
+            continue;
+        }
+        if rest[0].is_empty() {
+            alternatives.push(letter_id.to_string());
+        } else {
+            let rest_id = ids.next().expect("enough rule IDs");
+            add_rule(rules, ids, letters, rest_id, &rest);
+            alternatives.push(format!("{} {}", letter_id, rest_id));
+        }
+    }
+    rules.push(format!("{}: {}", id, alternatives.join(" | ")));
+}
+
+/// Every block of five letters matches either rule 42 or 31, so a message
+/// matches the rules when its blocks do, as in the puzzle.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let words: Vec<String> = (0..1 << BLOCK)
+        .map(|bits: u32| {
+            (0..BLOCK)
+                .map(|i| if bits >> i & 1 == 0 { 'a' } else { 'b' })
+                .collect()
+        })
+        .collect();
+    let (first, second) = loop {
+        let (first, second): (Vec<String>, Vec<String>) =
+            words.iter().cloned().partition(|_| rng.chance(50));
+        if !first.is_empty() && !second.is_empty() {
+            break (first, second);
+        }
+    };
+
+    let mut ids: Vec<u32> = (1..=150)
+        .filter(|id| ![8, 11, 31, 42].contains(id))
+        .collect();
+    rng.shuffle(&mut ids);
+    let mut ids = ids.into_iter();
+    let letters = (ids.next().unwrap(), ids.next().unwrap());
+    let mut rules = vec![
+        "0: 8 11".to_owned(),
+        "8: 42".to_owned(),
+        "11: 42 31".to_owned(),
+        format!("{}: \"a\"", letters.0),
+        format!("{}: \"b\"", letters.1),
+    ];
+    add_rule(&mut rules, &mut ids, letters, 42, &first);
+    add_rule(&mut rules, &mut ids, letters, 31, &second);
+    rng.shuffle(&mut rules);
+
+    let (mut matching1, mut matching2) = (0, 0);
+    let mut messages = Vec::new();
+    for _ in 0..size {
+        let blocks: Vec<bool> = match rng.index(3) {
+            0 => vec![true, true, false],
+            1 => {
+                let (ours, theirs) = (rng.range(1..=6), rng.range(0..=4));
+                (0..ours + theirs).map(|i| i < ours).collect()
+            }
+            _ => (0..rng.range(1..=8)).map(|_| rng.chance(50)).collect(),
+        };
+        let count = |first: bool| blocks.iter().filter(|&&block| block == first).count();
+        let (ours, theirs) = (count(true), count(false));
+        let ordered = blocks.iter().take(ours).all(|&block| block);
+        matching1 += usize::from(blocks == [true, true, false]);
+        matching2 += usize::from(ordered && theirs >= 1 && ours > theirs);
+
+        messages.push(
+            blocks
+                .iter()
+                .map(|&block| rng.choose(if block { &first } else { &second }).as_str())
+                .collect::<String>(),
+        );
+    }
+
+    Generated::new(format!(
+        "{}\n{}",
+        generate::lines(rules),
+        generate::lines(messages)
+    ))
+    .with_answer(1, matching1)
+    .with_answer(2, matching2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let (rules, messages) =
+            read_input(BufReader::new(File::open("inputs/day19/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&rules, &messages), 176);
+        assert_eq!(part2(&rules, &messages), 352);
+    }
+
+    #[test]
+    fn test_cycles() {
+        let error = |input: &str| read_input(input.as_bytes()).err().unwrap().to_string();
+        assert_eq!(
+            error("0: 1\n1: 0\n\na\n"),
+            "day 19, end of input: rule 0 refers back to itself"
+        );
+        assert_eq!(
+            error("0: 8 11\n8: 42\n11: 42 31\n42: 8 | 1\n31: 1\n1: \"a\"\n\na\n"),
+            "day 19, end of input: rule 8 refers back to itself"
+        );
+        assert_eq!(
+            error("0: 8\n8: 1\n42: 8\n1: \"a\"\n\na\n"),
+            "day 19, end of input: rule 8 refers back to itself with the loops of part 2"
+        );
+        assert!(error("0: 1 | \n1: \"a\"\n").contains("expected rule ids"));
+    }
+}
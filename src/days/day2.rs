@@ -0,0 +1,702 @@
+use std::{fmt, io::BufRead, str::FromStr};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    parse, Answers, Error, Param, Params, ParseError, Solution,
+};
+
+/// A line of the password database, `<first>-<second> <char>: <password>`.
+/// The character and the password may be any Unicode but whitespace.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PolicyPassword {
+    first: usize,
+    second: usize,
+    character: char,
+    pass: String,
+}
+
+impl PolicyPassword {
+    /// The character of the password at a position counting characters, not
+    /// bytes, from 1.
+    pub fn char_at(&self, position: usize) -> Result<char, PositionError> {
+        let index = position.checked_sub(1).ok_or(PositionError::Zero)?;
+        self.pass
+            .chars()
+            .nth(index)
+            .ok_or_else(|| PositionError::PastEnd {
+                position,
+                length: self.pass.chars().count(),
+            })
+    }
+}
+
+/// A position outside of the password, see [`PolicyPassword::char_at`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PositionError {
+    Zero,
+    PastEnd { position: usize, length: usize },
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::Zero => write!(f, "position 0 is out of range, positions start at 1"),
+            PositionError::PastEnd { position, length } => write!(
+                f,
+                "position {} is past the end of the {} character password",
+                position, length
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
+
+/// A rule a password has to follow. The numbers and character of its line
+/// are up to the rule, those with settings of their own may ignore them.
+///
+/// Rules display as the spec [`Policies`] parses them from.
+pub trait Policy: fmt::Display + Send + Sync {
+    /// Why the password of `entry` breaks the rule, `None` if it doesn't.
+    fn violation(&self, entry: &PolicyPassword) -> Option<String>;
+}
+
+/// The character occurs `first` to `second` times, the policy of part 1.
+pub struct Count;
+
+impl Policy for Count {
+    fn violation(&self, entry: &PolicyPassword) -> Option<String> {
+        let occurences = entry.pass.matches(entry.character).count();
+        if (entry.first..=entry.second).contains(&occurences) {
+            return None;
+        }
+        Some(format!(
+            "`{}` occurs {} times, expected {} to {}",
+            entry.character, occurences, entry.first, entry.second
+        ))
+    }
+}
+
+impl fmt::Display for Count {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "count")
+    }
+}
+
+/// The character is at exactly one of the positions `first` and `second`,
+/// the policy of part 2. Positions count characters from 1, not bytes.
+///
+/// A position of 0 or past the end of the password holds no character, so
+/// the character is never there. Such a password can still be valid through
+/// its other position.
+pub struct Positions;
+
+impl Policy for Positions {
+    fn violation(&self, entry: &PolicyPassword) -> Option<String> {
+        let first = entry.char_at(entry.first);
+        let second = entry.char_at(entry.second);
+        let reason = match (first == Ok(entry.character), second == Ok(entry.character)) {
+            (true, false) | (false, true) => return None,
+            (true, true) => format!(
+                "`{}` is at both positions {} and {}",
+                entry.character, entry.first, entry.second
+            ),
+            (false, false) => format!(
+                "`{}` is at neither position {} nor {}",
+                entry.character, entry.first, entry.second
+            ),
+        };
+        let out_of_range: Vec<_> = [first, second]
+            .iter()
+            .filter_map(|at| at.err().map(|e| e.to_string()))
+            .collect();
+        match out_of_range[..] {
+            [] => Some(reason),
+            _ => Some(format!("{}, {}", reason, out_of_range.join(", "))),
+        }
+    }
+}
+
+impl fmt::Display for Positions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "positions")
+    }
+}
+
+/// Characters [`AtLeast`] counts, following their Unicode properties.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CharClass {
+    Digit,
+    Lower,
+    Upper,
+    Letter,
+    /// Anything but letters, digits and whitespace.
+    Symbol,
+    NonAscii,
+}
+
+impl CharClass {
+    const NAMES: [(&'static str, CharClass); 6] = [
+        ("digit", CharClass::Digit),
+        ("lower", CharClass::Lower),
+        ("upper", CharClass::Upper),
+        ("letter", CharClass::Letter),
+        ("symbol", CharClass::Symbol),
+        ("non-ascii", CharClass::NonAscii),
+    ];
+
+    pub fn contains(self, ch: char) -> bool {
+        match self {
+            CharClass::Digit => ch.is_numeric(),
+            CharClass::Lower => ch.is_lowercase(),
+            CharClass::Upper => ch.is_uppercase(),
+            CharClass::Letter => ch.is_alphabetic(),
+            CharClass::Symbol => !ch.is_alphanumeric() && !ch.is_whitespace(),
+            CharClass::NonAscii => !ch.is_ascii(),
+        }
+    }
+}
+
+impl FromStr for CharClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::NAMES
+            .iter()
+            .find(|(name, _)| *name == s)
+            .map(|&(_, class)| class)
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::NAMES.iter().map(|(name, _)| *name).collect();
+                format!(
+                    "unknown character class `{}`, expected one of {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+impl fmt::Display for CharClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, _) = Self::NAMES.iter().find(|(_, class)| class == self).unwrap();
+        write!(f, "{}", name)
+    }
+}
+
+/// At least `count` characters of a class.
+pub struct AtLeast {
+    pub count: usize,
+    pub class: CharClass,
+}
+
+impl Policy for AtLeast {
+    fn violation(&self, entry: &PolicyPassword) -> Option<String> {
+        let count = entry
+            .pass
+            .chars()
+            .filter(|&ch| self.class.contains(ch))
+            .count();
+        if count >= self.count {
+            return None;
+        }
+        Some(format!(
+            "{} {} characters, expected at least {}",
+            count, self.class, self.count
+        ))
+    }
+}
+
+impl fmt::Display for AtLeast {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at-least:{}:{}", self.count, self.class)
+    }
+}
+
+/// None of the substrings.
+pub struct Forbidden {
+    pub substrings: Vec<String>,
+}
+
+impl Policy for Forbidden {
+    fn violation(&self, entry: &PolicyPassword) -> Option<String> {
+        let found: Vec<_> = self
+            .substrings
+            .iter()
+            .filter(|substring| entry.pass.contains(substring.as_str()))
+            .map(|substring| format!("`{}`", substring))
+            .collect();
+        if found.is_empty() {
+            return None;
+        }
+        Some(format!("contains {}", found.join(", ")))
+    }
+}
+
+impl fmt::Display for Forbidden {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "forbidden:{}", self.substrings.join(","))
+    }
+}
+
+/// No character more than `max` times in a row.
+pub struct MaxRepeats {
+    pub max: usize,
+}
+
+impl Policy for MaxRepeats {
+    fn violation(&self, entry: &PolicyPassword) -> Option<String> {
+        let mut longest: Option<(char, usize)> = None;
+        let mut run: Option<(char, usize)> = None;
+        for ch in entry.pass.chars() {
+            run = match run {
+                Some((last, count)) if last == ch => Some((ch, count + 1)),
+                _ => Some((ch, 1)),
+            };
+            if run.map(|(_, count)| count) > longest.map(|(_, count)| count) {
+                longest = run;
+            }
+        }
+        match longest {
+            Some((ch, count)) if count > self.max => Some(format!(
+                "`{}` repeats {} times in a row, expected at most {}",
+                ch, count, self.max
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MaxRepeats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "max-repeats:{}", self.max)
+    }
+}
+
+/// Rules a password has to follow all of, parsed from specs joined by `+`,
+/// e.g. `count+at-least:2:digit+forbidden:abc,xyz+max-repeats:3`.
+pub struct Policies(pub Vec<Box<dyn Policy>>);
+
+impl Policies {
+    pub fn is_valid(&self, entry: &PolicyPassword) -> bool {
+        self.0
+            .iter()
+            .all(|policy| policy.violation(entry).is_none())
+    }
+
+    /// Every rule `entry` breaks and why.
+    pub fn violations(&self, entry: &PolicyPassword) -> Vec<Violation> {
+        self.0
+            .iter()
+            .filter_map(|policy| {
+                policy.violation(entry).map(|reason| Violation {
+                    rule: policy.to_string(),
+                    reason,
+                })
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Policies {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let policy = |spec: &str| -> Result<Box<dyn Policy>, ParseError> {
+            let number = |value: &str| {
+                value.parse().map_err(|_| {
+                    ParseError::within(
+                        Day2::DAY,
+                        s,
+                        value,
+                        format!("expected a number in `{}`", spec),
+                    )
+                })
+            };
+            let policy: Box<dyn Policy> = match spec.split(':').collect::<Vec<_>>()[..] {
+                ["count"] => Box::new(Count),
+                ["positions"] => Box::new(Positions),
+                ["at-least", count, class] => Box::new(AtLeast {
+                    count: number(count)?,
+                    class: class
+                        .parse()
+                        .map_err(|e| ParseError::within(Day2::DAY, s, class, e))?,
+                }),
+                ["max-repeats", max] => Box::new(MaxRepeats { max: number(max)? }),
+                _ => match spec.strip_prefix("forbidden:") {
+                    Some(substrings) if substrings.split(',').any(str::is_empty) => {
+                        return Err(ParseError::within(
+                            Day2::DAY,
+                            s,
+                            spec,
+                            format!("empty substring in `{}`", spec),
+                        ))
+                    }
+                    Some(substrings) => Box::new(Forbidden {
+                        substrings: substrings.split(',').map(str::to_owned).collect(),
+                    }),
+                    None => {
+                        return Err(ParseError::within(
+                            Day2::DAY,
+                            s,
+                            spec,
+                            format!(
+                                "unknown policy `{}`, expected count, positions, \
+                                 at-least:N:CLASS, forbidden:A,B or max-repeats:N",
+                                spec
+                            ),
+                        ))
+                    }
+                },
+            };
+            Ok(policy)
+        };
+        s.split('+')
+            .map(policy)
+            .collect::<Result<_, _>>()
+            .map(Policies)
+    }
+}
+
+impl fmt::Display for Policies {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let specs: Vec<_> = self.0.iter().map(|policy| policy.to_string()).collect();
+        write!(f, "{}", specs.join("+"))
+    }
+}
+
+/// A rule a password breaks, as its spec, and why.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    pub rule: String,
+    pub reason: String,
+}
+
+/// The rules the password on a line breaks, none if it is valid.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LineReport {
+    pub line: usize,
+    pub entry: String,
+    pub violations: Vec<Violation>,
+}
+
+/// Checks the password on every line against `policies`.
+pub fn audit<R: BufRead>(reader: R, policies: &Policies) -> Result<Vec<LineReport>, Error> {
+    let mut reports = Vec::new();
+    for line in parse::lines(reader) {
+        let (num, line) = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let entry = PolicyPassword::from_str(&line).map_err(|e| e.on_line(num))?;
+        reports.push(LineReport {
+            line: num,
+            entry: line,
+            violations: policies.violations(&entry),
+        });
+    }
+    Ok(reports)
+}
+
+impl FromStr for PolicyPassword {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^([0-9]+)-([0-9]+)\s+(\S):\s+(\S+)$").unwrap();
+        }
+
+        let caps = RE.captures(s).ok_or_else(|| {
+            ParseError::line(Day2::DAY, s, "expected `<min>-<max> <char>: <password>`")
+        })?;
+        Ok(PolicyPassword {
+            first: parse::number(Day2::DAY, s, &caps[1])?,
+            second: parse::number(Day2::DAY, s, &caps[2])?,
+            character: caps[3].chars().next().unwrap_or_default(),
+            pass: caps[4].to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for PolicyPassword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{} {}: {}",
+            self.first, self.second, self.character, self.pass
+        )
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<PolicyPassword>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_policies_and_passwords(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        Self::part1_with(input, &Params::default())
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        Self::part2_with(input, &Params::default())
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+
+    const PARAMS: &'static [Param] = &[PART1_POLICY, PART2_POLICY];
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Self::Answer1 {
+        count_valid(input, &PART1_POLICY.get(params))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Self::Answer2 {
+        count_valid(input, &PART2_POLICY.get(params))
+    }
+
+    fn stream<R: BufRead>(reader: R, params: &Params) -> Option<Result<Answers<Self>, Error>> {
+        Some(stream(
+            reader,
+            &PART1_POLICY.get(params),
+            &PART2_POLICY.get(params),
+        ))
+    }
+}
+
+pub const PART1_POLICY: Param = Param {
+    name: "part1-policy",
+    default: "count",
+    help: "Rules the passwords of part 1 follow, e.g. `count+max-repeats:2`",
+    check: check_policies,
+};
+
+pub const PART2_POLICY: Param = Param {
+    name: "part2-policy",
+    default: "positions",
+    help: "Rules the passwords of part 2 follow, e.g. `positions+at-least:1:digit`",
+    check: check_policies,
+};
+
+fn check_policies(value: &str) -> Result<(), String> {
+    value.parse::<Policies>().map(|_| ()).map_err(|e| e.reason)
+}
+
+pub fn count_valid(policies_passwords: &[PolicyPassword], policies: &Policies) -> usize {
+    policies_passwords
+        .iter()
+        .filter(|l| policies.is_valid(l))
+        .count()
+}
+
+pub fn part1(policies_passwords: &[PolicyPassword]) -> usize {
+    count_valid(policies_passwords, &Policies(vec![Box::new(Count)]))
+}
+
+pub fn part2(policies_passwords: &[PolicyPassword]) -> usize {
+    count_valid(policies_passwords, &Policies(vec![Box::new(Positions)]))
+}
+
+/// Counts the valid passwords of both parts line by line.
+pub fn stream<R: BufRead>(
+    reader: R,
+    policies1: &Policies,
+    policies2: &Policies,
+) -> Result<(usize, usize), Error> {
+    let mut valid = (0, 0);
+    for policy_password in parse::records(reader, PolicyPassword::from_str) {
+        let policy_password = policy_password?;
+        valid.0 += usize::from(policies1.is_valid(&policy_password));
+        valid.1 += usize::from(policies2.is_valid(&policy_password));
+    }
+    Ok(valid)
+}
+
+pub fn read_policies_and_passwords<R: BufRead>(reader: R) -> Result<Vec<PolicyPassword>, Error> {
+    parse::records(reader, PolicyPassword::from_str).collect()
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "passwords",
+    sizes: 1..=5000,
+    default_size: 1000,
+    build: generate,
+};
+
+/// Passwords at least as long as the second position of their policy, so
+/// both policies apply to every one of them.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let (mut valid1, mut valid2) = (0, 0);
+    let mut lines = Vec::new();
+    for _ in 0..size {
+        let first = rng.range(1..=10) as usize;
+        let second = rng.range(first as i64..=20) as usize;
+        let character = generate::word(rng, 1).chars().next().unwrap();
+        let len = rng.range(second as i64..=24) as usize;
+        let pass: String = (0..len)
+            .map(|_| {
+                if rng.chance(40) {
+                    character
+                } else {
+                    generate::word(rng, 1).chars().next().unwrap()
+                }
+            })
+            .collect();
+
+        let count = pass.matches(character).count();
+        valid1 += usize::from((first..=second).contains(&count));
+        let at = |position: usize| pass.as_bytes()[position - 1] == character as u8;
+        valid2 += usize::from(at(first) != at(second));
+        lines.push(PolicyPassword {
+            first,
+            second,
+            character,
+            pass,
+        });
+    }
+    Generated::new(generate::lines(lines))
+        .with_answer(1, valid1)
+        .with_answer(2, valid2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let input =
+            read_policies_and_passwords(BufReader::new(File::open("inputs/day2/1.txt").unwrap()))
+                .unwrap();
+        assert_eq!(part1(&input), 640);
+        assert_eq!(part2(&input), 472);
+    }
+
+    #[test]
+    fn test_policies() {
+        let policies: Policies = "count+at-least:2:digit+forbidden:ab,cd+max-repeats:2"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            policies.to_string(),
+            "count+at-least:2:digit+forbidden:ab,cd+max-repeats:2"
+        );
+        let entry: PolicyPassword = "1-3 a: aaab1".parse().unwrap();
+        let rules: Vec<_> = policies
+            .violations(&entry)
+            .into_iter()
+            .map(|violation| violation.reason)
+            .collect();
+        assert_eq!(
+            rules,
+            [
+                "1 digit characters, expected at least 2",
+                "contains `ab`",
+                "`a` repeats 3 times in a row, expected at most 2",
+            ]
+        );
+        assert!(policies.is_valid(&"1-3 a: a1b2a".parse().unwrap()));
+
+        let reports = audit(
+            "1-3 a: abcde\n\n1-3 b: cdefg\n".as_bytes(),
+            &"count".parse().unwrap(),
+        )
+        .unwrap();
+        assert!(reports[0].violations.is_empty());
+        assert_eq!(reports[1].line, 3);
+        assert_eq!(
+            reports[1].violations[0].reason,
+            "`b` occurs 0 times, expected 1 to 3"
+        );
+        assert!("at-least:x:digit".parse::<Policies>().is_err());
+        let error = match "count+forbidden:a,,b".parse::<Policies>() {
+            Err(error) => error,
+            Ok(policies) => panic!("accepted {}", policies),
+        };
+        assert_eq!(error.column, 7);
+        assert_eq!(error.reason, "empty substring in `forbidden:a,,b`");
+        assert!("forbidden:a,".parse::<Policies>().is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn policy_password_round_trips(
+            first in 0..100usize,
+            second in 0..100usize,
+            character in "[a-z]",
+            pass in "[a-z]{1,20}",
+        ) {
+            let policy = PolicyPassword {
+                first,
+                second,
+                character: character.chars().next().unwrap(),
+                pass,
+            };
+            prop_assert_eq!(policy.to_string().parse::<PolicyPassword>().unwrap(), policy);
+        }
+
+        #[test]
+        fn unicode_policy_password_round_trips(
+            first in 0..100usize,
+            second in 0..100usize,
+            character in "\\S",
+            pass in "\\S{1,20}",
+        ) {
+            let policy = PolicyPassword {
+                first,
+                second,
+                character: character.chars().next().unwrap(),
+                pass,
+            };
+            prop_assert_eq!(policy.to_string().parse::<PolicyPassword>().unwrap(), policy.clone());
+            Positions.violation(&policy);
+        }
+    }
+
+    #[test]
+    fn test_unicode_and_out_of_range() {
+        let entry: PolicyPassword = "2-3 ü: ñüü€".parse().unwrap();
+        assert_eq!(entry.char_at(4), Ok('€'));
+        assert!(Count.violation(&entry).is_none());
+        assert_eq!(
+            Positions.violation(&entry).unwrap(),
+            "`ü` is at both positions 2 and 3"
+        );
+
+        let entry: PolicyPassword = "0-9 a: abc".parse().unwrap();
+        assert_eq!(entry.char_at(0), Err(PositionError::Zero));
+        assert_eq!(
+            entry.char_at(9),
+            Err(PositionError::PastEnd {
+                position: 9,
+                length: 3
+            })
+        );
+        assert!(Positions.violation(&entry).is_some());
+        assert!(Positions
+            .violation(&"1-9 a: abc".parse().unwrap())
+            .is_none());
+        assert_eq!(
+            Positions.violation(&"0-4 b: abc".parse().unwrap()).unwrap(),
+            "`b` is at neither position 0 nor 4, position 0 is out of range, positions start \
+             at 1, position 4 is past the end of the 3 character password"
+        );
+
+        let error = "1-3 a: ab c".parse::<PolicyPassword>().unwrap_err();
+        assert_eq!(error.reason, "expected `<min>-<max> <char>: <password>`");
+        assert!("99999999999999999999-3 a: abc"
+            .parse::<PolicyPassword>()
+            .is_err());
+    }
+}
@@ -0,0 +1,386 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::BufRead,
+    iter,
+};
+
+use crate::{
+    generate::{Generated, Generator, Rng},
+    grid::{Grid, Position},
+    parse,
+    render::{Cell, Frame, Rgb},
+    Error, Params, ParseError, Solution,
+};
+
+pub type TileData = Grid<char>;
+
+#[derive(Debug)]
+pub struct Tile {
+    id: u32,
+    data: TileData,
+}
+
+/// The sea monster, `#` marks the parts that are on rough water.
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input = Vec<Tile>;
+    type Answer1 = u64;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_tiles(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+}
+
+pub fn part1(tiles: &[Tile]) -> u64 {
+    let counts = border_counts(tiles);
+    tiles
+        .iter()
+        .filter(|tile| unmatched(&counts, &tile.data) == 2)
+        .map(|tile| u64::from(tile.id))
+        .product()
+}
+
+pub fn part2(tiles: &[Tile]) -> usize {
+    let (image, monsters) = find_monsters(tiles);
+    image.iter().filter(|(_, &pixel)| pixel == '#').count() - monsters.len()
+}
+
+/// The assembled image turned so that the sea monsters show, together with
+/// the pixels they cover.
+fn find_monsters(tiles: &[Tile]) -> (Grid<char>, HashSet<Position>) {
+    let image = assemble(tiles).expect("tiles don't fit together");
+    let monster: Vec<Position> = MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.char_indices()
+                .filter(|&(_, ch)| ch == '#')
+                .map(move |(x, _)| (x, y))
+        })
+        .collect();
+
+    for image in image.orientations() {
+        let mut seen = HashSet::new();
+        for (x, y) in image.positions() {
+            let parts: Vec<_> = monster.iter().map(|&(dx, dy)| (x + dx, y + dy)).collect();
+            if parts.iter().all(|&part| image.get(part) == Some(&'#')) {
+                seen.extend(parts);
+            }
+        }
+        if !seen.is_empty() {
+            return (image, seen);
+        }
+    }
+    (image, HashSet::new())
+}
+
+/// The assembled image, with the sea monsters in yellow.
+pub fn frame(image: &Grid<char>, monsters: &HashSet<Position>) -> Frame {
+    Grid::from_fn(image.width(), image.height(), |position| {
+        match image[position] {
+            _ if monsters.contains(&position) => Cell::new('O', Rgb(250, 210, 50)),
+            '#' => Cell::new('#', Rgb(70, 130, 210)),
+            _ => Cell::new('.', Rgb(15, 45, 110)),
+        }
+    })
+}
+
+pub fn animation(tiles: &[Tile], _part: u8, _params: &Params) -> Box<dyn Iterator<Item = Frame>> {
+    let (image, monsters) = find_monsters(tiles);
+    Box::new(iter::once(frame(&image, &monsters)))
+}
+
+/// Top, right, bottom and left border, read left to right and top to bottom.
+fn borders(data: &TileData) -> [String; 4] {
+    [
+        data.row(0).iter().collect(),
+        data.column(data.width() - 1).collect(),
+        data.row(data.height() - 1).iter().collect(),
+        data.column(0).collect(),
+    ]
+}
+
+/// A border and its reverse are the same border of a flipped tile.
+fn canonical(border: &str) -> String {
+    let reversed: String = border.chars().rev().collect();
+    reversed.min(border.to_owned())
+}
+
+/// How many tiles have each border.
+fn border_counts(tiles: &[Tile]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for tile in tiles {
+        for border in borders(&tile.data).iter() {
+            *counts.entry(canonical(border)).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Number of borders no other tile shares, these are on the edge of the image.
+fn unmatched(counts: &HashMap<String, usize>, data: &TileData) -> usize {
+    borders(data)
+        .iter()
+        .filter(|border| counts[&canonical(border)] == 1)
+        .count()
+}
+
+/// Lays the tiles out row by row starting from a corner, then strips their
+/// borders. Every border is assumed to match at most one other tile.
+fn assemble(tiles: &[Tile]) -> Option<Grid<char>> {
+    let side = (1..=tiles.len()).find(|side| side * side >= tiles.len())?;
+    let size = tiles[0].data.width();
+    if side * side != tiles.len() || tiles.iter().any(|tile| tile.data.width() != size) {
+        return None;
+    }
+
+    let counts = border_counts(tiles);
+    let is_edge = |border: &String| counts[&canonical(border)] == 1;
+    let (corner_index, corner) = tiles
+        .iter()
+        .enumerate()
+        .find(|(_, tile)| unmatched(&counts, &tile.data) == 2)?;
+    let corner = corner.data.orientations().find(|data| {
+        let [top, _, _, left] = borders(data);
+        is_edge(&top) && is_edge(&left)
+    })?;
+
+    let mut used = HashSet::new();
+    used.insert(corner_index);
+    let mut placed = vec![corner];
+    for i in 1..side * side {
+        let left = Some(i % side)
+            .filter(|&x| x > 0)
+            .map(|_| borders(&placed[i - 1])[1].clone());
+        let top = i
+            .checked_sub(side)
+            .map(|above| borders(&placed[above])[2].clone());
+        let (index, data) = tiles
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !used.contains(index))
+            .flat_map(|(index, tile)| tile.data.orientations().map(move |data| (index, data)))
+            .find(|(_, data)| {
+                let [this_top, _, _, this_left] = borders(data);
+                left.as_ref().is_none_or(|left| *left == this_left)
+                    && top.as_ref().is_none_or(|top| *top == this_top)
+            })?;
+        used.insert(index);
+        placed.push(data);
+    }
+
+    let inner = size.saturating_sub(2);
+    Some(Grid::from_fn(side * inner, side * inner, |(x, y)| {
+        placed[y / inner * side + x / inner][(x % inner + 1, y % inner + 1)]
+    }))
+}
+
+fn pixel(ch: char) -> Option<char> {
+    Some(ch).filter(|ch| matches!(ch, '.' | '#'))
+}
+
+fn finish_tile(id: Option<u32>, data: TileData) -> Result<Tile, ParseError> {
+    let id = id.ok_or_else(|| ParseError::end_of_input(Day20::DAY, "missing tile header"))?;
+    if data.is_empty() || data.width() != data.height() {
+        return Err(ParseError::end_of_input(
+            Day20::DAY,
+            format!("tile {} is not a non-empty square", id),
+        ));
+    }
+    Ok(Tile { id, data })
+}
+
+pub fn read_tiles<R: BufRead>(reader: R) -> Result<Vec<Tile>, Error> {
+    let mut tiles = Vec::new();
+    let mut current_id = None;
+    let mut current_data = TileData::empty();
+    for line in parse::lines(reader) {
+        let (num, line) = line?;
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix("Tile ") {
+            if current_id.is_some() {
+                tiles.push(finish_tile(current_id, current_data)?);
+                current_data = TileData::empty();
+            }
+            let id = header.strip_suffix(':').ok_or_else(|| {
+                ParseError::line(Day20::DAY, &line, "expected `Tile <id>:`").on_line(num)
+            })?;
+            current_id = Some(parse::number(Day20::DAY, &line, id).map_err(|e| e.on_line(num))?);
+        } else {
+            let row = Grid::parse_row(Day20::DAY, &line, pixel, "expected `.` or `#`")
+                .map_err(|e| e.on_line(num))?;
+            current_data
+                .push_row(row)
+                .map_err(|e| ParseError::line(Day20::DAY, &line, e).on_line(num))?;
+        }
+    }
+    tiles.push(finish_tile(current_id, current_data)?);
+
+    Ok(tiles)
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "tiles per side",
+    sizes: 3..=12,
+    default_size: 12,
+    build: generate,
+};
+
+/// Matches of the sea monster in `image`.
+fn count_monsters(image: &Grid<char>) -> usize {
+    let width = MONSTER[0].len();
+    (0..image.height().saturating_sub(MONSTER.len() - 1))
+        .flat_map(|y| (0..image.width().saturating_sub(width - 1)).map(move |x| (x, y)))
+        .filter(|&(x, y)| {
+            MONSTER.iter().enumerate().all(|(dy, row)| {
+                row.char_indices()
+                    .all(|(dx, ch)| ch != '#' || image[(x + dx, y + dy)] == '#')
+            })
+        })
+        .count()
+}
+
+/// Cuts a random image with sea monsters into tiles. Neighbouring tiles share
+/// a border which no other tile has, in any orientation, and which reads
+/// differently backwards, so there is only one way to assemble them.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    const INNER: usize = 8;
+    let side = INNER * size;
+
+    let (image, monsters) = loop {
+        let mut image = Grid::from_fn(side, side, |_| if rng.chance(30) { '#' } else { '.' });
+        let mut planted: Vec<Position> = Vec::new();
+        for _ in 0..size * size / 2 {
+            let (x, y) = (rng.index(side - 19), rng.index(side - 2));
+            if planted
+                .iter()
+                .all(|&(px, py)| x.abs_diff(px) >= 20 || y.abs_diff(py) >= 3)
+            {
+                planted.push((x, y));
+            }
+        }
+        for &(x, y) in &planted {
+            for (dy, row) in MONSTER.iter().enumerate() {
+                for (dx, _) in row.char_indices().filter(|&(_, ch)| ch == '#') {
+                    image[(x + dx, y + dy)] = '#';
+                }
+            }
+        }
+        if count_monsters(&image) == planted.len()
+            && image
+                .orientations()
+                .map(|image| count_monsters(&image))
+                .sum::<usize>()
+                == planted.len()
+        {
+            break (image, planted.len());
+        }
+    };
+
+    // Tiles overlap on their borders, the pixels between them are the image.
+    let full = 9 * size + 1;
+    let mut pixels = Grid::from_fn(full, full, |(x, y)| {
+        if x % 9 == 0 || y % 9 == 0 {
+            if rng.chance(50) {
+                '#'
+            } else {
+                '.'
+            }
+        } else {
+            image[(x / 9 * INNER + x % 9 - 1, y / 9 * INNER + y % 9 - 1)]
+        }
+    });
+    let border = |(x, y): Position, vertical: bool| -> Vec<Position> {
+        (0..10)
+            .map(|i| if vertical { (x, y + i) } else { (x + i, y) })
+            .collect()
+    };
+    let borders: Vec<Vec<Position>> = (0..=size)
+        .flat_map(|line| (0..size).map(move |tile| (9 * line, 9 * tile)))
+        .flat_map(|(line, tile)| vec![border((tile, line), false), border((line, tile), true)])
+        .collect();
+    loop {
+        let read = |positions: &Vec<Position>| -> String {
+            positions.iter().map(|&position| pixels[position]).collect()
+        };
+        let mut seen = HashSet::new();
+        let clashing = borders.iter().find(|positions| {
+            let border = read(positions);
+            border.chars().rev().collect::<String>() == border || !seen.insert(canonical(&border))
+        });
+        match clashing {
+            Some(positions) => {
+                for &position in &positions[1..9] {
+                    pixels[position] = if rng.chance(50) { '#' } else { '.' };
+                }
+            }
+            None => break,
+        }
+    }
+
+    let mut ids: Vec<u32> = (1000..10000).collect();
+    rng.shuffle(&mut ids);
+    let mut tiles: Vec<(u32, Position)> = ids
+        .into_iter()
+        .zip((0..size * size).map(|i| (i % size, i / size)))
+        .collect();
+    let corners = tiles
+        .iter()
+        .filter(|(_, (x, y))| (*x == 0 || *x == size - 1) && (*y == 0 || *y == size - 1))
+        .map(|&(id, _)| u64::from(id))
+        .product::<u64>();
+    let roughness = image.iter().filter(|(_, &pixel)| pixel == '#').count() - 15 * monsters;
+
+    rng.shuffle(&mut tiles);
+    let input = tiles
+        .iter()
+        .map(|&(id, (x, y))| {
+            let orientation = rng.index(8);
+            let data = pixels
+                .crop((9 * x, 9 * y), 10, 10)
+                .orientations()
+                .nth(orientation)
+                .unwrap();
+            format!("Tile {}:\n{}\n\n", id, data)
+        })
+        .collect::<String>();
+    Generated::new(input)
+        .with_answer(1, corners)
+        .with_answer(2, roughness)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let tiles = read_tiles(BufReader::new(File::open("inputs/day20/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&tiles), 19955159604613);
+        assert_eq!(part2(&tiles), 1639);
+    }
+}
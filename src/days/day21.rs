@@ -0,0 +1,309 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+    io::BufRead,
+    str::FromStr,
+};
+
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    parse, Error, ParseError, Solution,
+};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Food {
+    ingredients: HashSet<String>,
+    allergens: HashSet<String>,
+}
+
+impl FromStr for Food {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref FOOD_RE: Regex = Regex::new(r"^(.*) \(contains (.*)\)$").unwrap();
+        }
+
+        if let Some(caps) = FOOD_RE.captures(s) {
+            Ok(Food {
+                ingredients: caps[1].split(' ').map(|s| s.to_owned()).collect(),
+                allergens: caps[2].split(", ").map(|s| s.to_owned()).collect(),
+            })
+        } else {
+            Err(ParseError::line(
+                Day21::DAY,
+                s,
+                "expected `<ingredients> (contains <allergens>)`",
+            ))
+        }
+    }
+}
+
+/// Ingredients and allergens in alphabetical order.
+impl fmt::Display for Food {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (contains {})",
+            self.ingredients.iter().sorted().join(" "),
+            self.allergens.iter().sorted().join(", ")
+        )
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Input = Vec<Food>;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_food(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+}
+
+fn allergens_to_possible_ingredients(foods: &[Food]) -> HashMap<String, HashSet<String>> {
+    let mut allergens_to_possible_ingredients: HashMap<String, HashSet<String>> = HashMap::new();
+    for food in foods.iter() {
+        for allergen in food.allergens.iter() {
+            if let Some(possible_ingredients) = allergens_to_possible_ingredients.get_mut(allergen)
+            {
+                *possible_ingredients = possible_ingredients
+                    .intersection(&food.ingredients)
+                    .cloned()
+                    .collect();
+            } else {
+                allergens_to_possible_ingredients
+                    .insert(allergen.to_owned(), food.ingredients.clone());
+            }
+        }
+    }
+
+    allergens_to_possible_ingredients
+}
+
+pub fn part1(foods: &[Food]) -> usize {
+    let allergens_to_possible_ingredients = allergens_to_possible_ingredients(foods);
+
+    let mut allergens: HashSet<String> = HashSet::new();
+    for (_, possible_ingredients) in allergens_to_possible_ingredients.iter() {
+        allergens = allergens.union(possible_ingredients).cloned().collect();
+    }
+
+    let all_ingredients = foods.iter().fold(HashSet::new(), |all, food| {
+        all.union(&food.ingredients).cloned().collect()
+    });
+    let safe: HashSet<String> = all_ingredients.difference(&allergens).cloned().collect();
+    foods.iter().fold(0, |sum, food| {
+        sum + safe.intersection(&food.ingredients).count()
+    })
+}
+pub fn part2(foods: &[Food]) -> String {
+    let mut foods = foods.to_vec();
+    let all_ingredients = foods.iter().fold(HashSet::new(), |all, food| {
+        all.union(&food.ingredients).cloned().collect()
+    });
+
+    let allergens_to_possible_ingredients = allergens_to_possible_ingredients(&foods);
+    let mut ingredients_allergens: HashMap<String, String> = HashMap::new();
+
+    let mut found_any = true;
+    while found_any {
+        found_any = false;
+
+        // Find an allergen
+        for (allergen, _) in allergens_to_possible_ingredients.iter() {
+            let mut ingredients = all_ingredients.clone();
+            for food in foods.iter() {
+                if food.allergens.contains(allergen) {
+                    ingredients = ingredients
+                        .intersection(&food.ingredients)
+                        .cloned()
+                        .collect();
+                }
+            }
+
+            if ingredients.len() == 1 {
+                let ingredient = ingredients.iter().next().unwrap();
+                ingredients_allergens.insert(ingredient.clone(), allergen.clone());
+                found_any = true;
+                break;
+            }
+        }
+
+        // Update the food list
+        for food in foods.iter_mut() {
+            for (ingredient, _) in ingredients_allergens.iter() {
+                let to_set = |ingredient: &str| {
+                    let mut result: HashSet<String> = HashSet::new();
+                    result.insert(ingredient.to_owned());
+                    result
+                };
+
+                food.ingredients = food
+                    .ingredients
+                    .difference(&to_set(ingredient))
+                    .cloned()
+                    .collect();
+                food.allergens = food
+                    .allergens
+                    .difference(&to_set(&ingredients_allergens[ingredient]))
+                    .cloned()
+                    .collect();
+            }
+        }
+    }
+
+    let mut result: BTreeMap<String, String> = BTreeMap::new();
+    for (k, v) in ingredients_allergens.iter() {
+        result.insert(v.clone(), k.clone());
+    }
+    result.values().join(",")
+}
+
+pub fn read_food<R: BufRead>(reader: R) -> Result<Vec<Food>, Error> {
+    let mut foods = Vec::new();
+    for line in parse::lines(reader) {
+        let (num, line) = line?;
+        if !line.is_empty() {
+            foods.push(Food::from_str(&line).map_err(|e| e.on_line(num))?);
+        }
+    }
+    Ok(foods)
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "foods",
+    sizes: 20..=200,
+    default_size: 40,
+    build: generate,
+};
+
+/// Each allergen is in one ingredient, which is in every food listing the
+/// allergen and in a few others. Inputs where the lists don't tell which
+/// ingredient has which allergen are thrown away.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    const ALLERGENS: [&str; 8] = [
+        "dairy",
+        "eggs",
+        "fish",
+        "nuts",
+        "peanuts",
+        "sesame",
+        "shellfish",
+        "soy",
+    ];
+    loop {
+        let mut names = HashSet::new();
+        while names.len() < 100 + ALLERGENS.len() {
+            let len = rng.range(4..=8) as usize;
+            names.insert(generate::word(rng, len));
+        }
+        let mut names: Vec<String> = names.into_iter().sorted().collect();
+        rng.shuffle(&mut names);
+        let safe = names.split_off(ALLERGENS.len());
+        let dangerous = names;
+
+        let foods: Vec<Food> = (0..size)
+            .map(|_| {
+                let mut allergens = HashSet::new();
+                let mut ingredients: HashSet<String> =
+                    safe.iter().filter(|_| rng.chance(20)).cloned().collect();
+                for (allergen, ingredient) in ALLERGENS.iter().zip(&dangerous) {
+                    if rng.chance(25) {
+                        allergens.insert(allergen.to_string());
+                        ingredients.insert(ingredient.clone());
+                    } else if rng.chance(30) {
+                        ingredients.insert(ingredient.clone());
+                    }
+                }
+                if allergens.is_empty() {
+                    let i = rng.index(ALLERGENS.len());
+                    allergens.insert(ALLERGENS[i].to_owned());
+                    ingredients.insert(dangerous[i].clone());
+                }
+                Food {
+                    ingredients,
+                    allergens,
+                }
+            })
+            .collect();
+
+        let candidates = allergens_to_possible_ingredients(&foods);
+        if candidates.len() < ALLERGENS.len()
+            || candidates.values().any(|ingredients| {
+                ingredients
+                    .iter()
+                    .any(|ingredient| safe.contains(ingredient))
+            })
+        {
+            continue;
+        }
+        let mut found: HashSet<&String> = HashSet::new();
+        while let Some(ingredient) = candidates.values().find_map(|ingredients| {
+            let left: Vec<&String> = ingredients.iter().filter(|i| !found.contains(i)).collect();
+            match left[..] {
+                [ingredient] => Some(ingredient),
+                _ => None,
+            }
+        }) {
+            found.insert(ingredient);
+        }
+        if found.len() < ALLERGENS.len() {
+            continue;
+        }
+
+        let unsafe_count: usize = foods
+            .iter()
+            .map(|food| food.ingredients.iter().filter(|i| safe.contains(i)).count())
+            .sum();
+        return Generated::new(generate::lines(&foods))
+            .with_answer(1, unsafe_count)
+            .with_answer(2, dangerous.join(","));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let foods = read_food(BufReader::new(File::open("inputs/day21/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&foods), 2874);
+        assert_eq!(
+            part2(&foods),
+            "gfvrr,ndkkq,jxcxh,bthjz,sgzr,mbkbn,pkkg,mjbtz"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn food_round_trips(
+            ingredients in prop::collection::hash_set("[a-z]{1,8}", 1..6),
+            allergens in prop::collection::hash_set("[a-z]{1,8}", 1..4),
+        ) {
+            let food = Food { ingredients, allergens };
+            prop_assert_eq!(food.to_string().parse::<Food>().unwrap(), food);
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashSet, VecDeque},
+    io::BufRead,
+};
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    parse, Error, ParseError, Solution,
+};
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+
+    type Input = (VecDeque<u32>, VecDeque<u32>);
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_decks(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input.0.clone(), input.1.clone())
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input.0.clone(), input.1.clone())
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+}
+
+pub fn part1(deck1: VecDeque<u32>, deck2: VecDeque<u32>) -> u32 {
+    let (_, deck) = combat(deck1, deck2).expect("an endless game");
+    score(&deck)
+}
+
+/// Plays Combat until a player runs out of cards, returns the winner and
+/// their deck. `None` if the decks come back to an earlier state, as the game
+/// would never end.
+fn combat(mut deck1: VecDeque<u32>, mut deck2: VecDeque<u32>) -> Option<(u32, VecDeque<u32>)> {
+    let mut seen = HashSet::new();
+    while !deck1.is_empty() && !deck2.is_empty() {
+        if !seen.insert((deck1.clone(), deck2.clone())) {
+            return None;
+        }
+
+        let card1 = deck1.pop_front().unwrap();
+        let card2 = deck2.pop_front().unwrap();
+
+        match card1.cmp(&card2) {
+            Ordering::Greater => {
+                deck1.push_back(card1);
+                deck1.push_back(card2);
+            }
+            Ordering::Less => {
+                deck2.push_back(card2);
+                deck2.push_back(card1);
+            }
+            Ordering::Equal => panic!("a draw!"),
+        }
+    }
+    if deck1.is_empty() {
+        Some((2, deck2))
+    } else {
+        Some((1, deck1))
+    }
+}
+
+fn score(deck: &VecDeque<u32>) -> u32 {
+    deck.iter()
+        .rev()
+        .enumerate()
+        .fold(0, |sum, (i, v)| sum + (i as u32 + 1) * v)
+}
+
+/// Plays Recursive Combat, returns the winner and both decks at the end of
+/// the game. A game ended by a repeated round leaves cards in both decks.
+fn play_game(
+    mut deck1: VecDeque<u32>,
+    mut deck2: VecDeque<u32>,
+) -> (u32, VecDeque<u32>, VecDeque<u32>) {
+    let mut cache: HashSet<(VecDeque<u32>, VecDeque<u32>)> = HashSet::new();
+    while !deck1.is_empty() && !deck2.is_empty() {
+        let current = (deck1.clone(), deck2.clone());
+        if !cache.insert(current) {
+            return (1, deck1, deck2);
+        }
+
+        let card1 = deck1.pop_front().unwrap();
+        let card2 = deck2.pop_front().unwrap();
+
+        let result = if card1 <= deck1.len() as u32 && card2 <= deck2.len() as u32 {
+            play_game(
+                deck1.iter().take(card1 as usize).copied().collect(),
+                deck2.iter().take(card2 as usize).copied().collect(),
+            )
+            .0
+        } else if card1 > card2 {
+            1
+        } else {
+            2
+        };
+
+        if result == 1 {
+            deck1.push_back(card1);
+            deck1.push_back(card2);
+        } else {
+            deck2.push_back(card2);
+            deck2.push_back(card1);
+        }
+    }
+
+    let winner = if deck1.is_empty() { 2 } else { 1 };
+    (winner, deck1, deck2)
+}
+
+pub fn part2(deck1: VecDeque<u32>, deck2: VecDeque<u32>) -> u32 {
+    match play_game(deck1, deck2) {
+        (1, deck, _) | (_, _, deck) => score(&deck),
+    }
+}
+
+pub fn read_decks<R: BufRead>(reader: R) -> Result<(VecDeque<u32>, VecDeque<u32>), Error> {
+    let mut deck1 = VecDeque::new();
+    let mut deck2 = VecDeque::new();
+    let mut seen = HashSet::new();
+
+    let mut section = 0;
+    for line in parse::lines(reader) {
+        let (num, line) = line?;
+        if line.is_empty() {
+            section += 1;
+            continue;
+        }
+
+        if line.starts_with('P') {
+            continue;
+        }
+
+        let card = parse::number(Day22::DAY, &line, &line).map_err(|e| e.on_line(num))?;
+        if !seen.insert(card) {
+            return Err(ParseError::line(Day22::DAY, &line, "duplicate card")
+                .on_line(num)
+                .into());
+        }
+
+        if section == 0 {
+            deck1.push_back(card);
+        } else {
+            deck2.push_back(card);
+        }
+    }
+
+    Ok((deck1, deck2))
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "cards per deck",
+    sizes: 1..=25,
+    default_size: 25,
+    build: generate,
+};
+
+/// Shuffled decks whose game of plain Combat ends.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let mut cards: Vec<u32> = (1..=2 * size as u32).collect();
+    loop {
+        rng.shuffle(&mut cards);
+        let (deck1, deck2) = cards.split_at(size);
+        if combat(
+            deck1.iter().copied().collect(),
+            deck2.iter().copied().collect(),
+        )
+        .is_some()
+        {
+            return Generated::new(format!(
+                "Player 1:\n{}\nPlayer 2:\n{}",
+                generate::lines(deck1),
+                generate::lines(deck2)
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let (deck1, deck2) =
+            read_decks(BufReader::new(File::open("inputs/day22/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(deck1.clone(), deck2.clone()), 30138);
+        assert_eq!(part2(deck1, deck2), 31587);
+    }
+
+    proptest! {
+        #[test]
+        fn games_keep_every_card(
+            cards in (2..12u32).prop_flat_map(|n| Just((1..=n).collect::<Vec<_>>()).prop_shuffle()),
+            split in 1..11usize,
+        ) {
+            let split = split.min(cards.len() - 1);
+            let deck1: VecDeque<_> = cards[..split].iter().copied().collect();
+            let deck2: VecDeque<_> = cards[split..].iter().copied().collect();
+            let all: Vec<_> = cards.iter().copied().sorted().collect();
+
+            if let Some((_, deck)) = combat(deck1.clone(), deck2.clone()) {
+                prop_assert_eq!(deck.into_iter().sorted().collect::<Vec<_>>(), all.clone());
+            }
+            let (_, deck1, deck2) = play_game(deck1, deck2);
+            prop_assert_eq!(deck1.into_iter().chain(deck2).sorted().collect::<Vec<_>>(), all);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::io::BufRead;
+
+use crate::{
+    generate::{Generated, Generator, Rng},
+    params, parse, Error, Param, Params, ParseError, Solution,
+};
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Input = Vec<u32>;
+    type Answer1 = i32;
+    type Answer2 = u64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_order(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        Self::part1_with(input, &Params::default())
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        Self::part2_with(input, &Params::default())
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+
+    const PARAMS: &'static [Param] = &[PART1_MOVES, PART2_CUPS, PART2_MOVES];
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Self::Answer1 {
+        part1(input, PART1_MOVES.get(params))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Self::Answer2 {
+        part2(input, PART2_CUPS.get(params), PART2_MOVES.get(params))
+    }
+}
+
+pub const PART1_MOVES: Param = Param {
+    name: "part1-moves",
+    default: "100",
+    help: "Moves of the crab in part 1",
+    check: params::between::<usize, 0, 1_000_000_000>,
+};
+
+pub const PART2_CUPS: Param = Param {
+    name: "part2-cups",
+    default: "1000000",
+    help: "Cups in the circle in part 2, at least the labelled ones",
+    // Every cup takes four bytes.
+    check: params::between::<u32, 0, 100_000_000>,
+};
+
+pub const PART2_MOVES: Param = Param {
+    name: "part2-moves",
+    default: "10000000",
+    help: "Moves of the crab in part 2",
+    check: params::between::<usize, 0, 1_000_000_000>,
+};
+
+/// The circle after `moves` moves, as the cup clockwise of every cup indexed
+/// by its label. The cups of `order` are followed by the rest of the labels
+/// up to `cups`.
+fn play(order: &[u32], cups: u32, moves: usize) -> Vec<u32> {
+    let highest = cups.max(order.len() as u32);
+    let labels: Vec<u32> = order
+        .iter()
+        .copied()
+        .chain(order.len() as u32 + 1..=highest)
+        .collect();
+    let mut next = vec![0; highest as usize + 1];
+    for (i, &cup) in labels.iter().enumerate() {
+        next[cup as usize] = labels[(i + 1) % labels.len()];
+    }
+
+    let mut current = order[0];
+    for _ in 0..moves {
+        let cup1 = next[current as usize];
+        let cup2 = next[cup1 as usize];
+        let cup3 = next[cup2 as usize];
+
+        let mut destination = current;
+        loop {
+            destination = if destination == 1 {
+                highest
+            } else {
+                destination - 1
+            };
+            if destination != cup1 && destination != cup2 && destination != cup3 {
+                break;
+            }
+        }
+
+        next[current as usize] = next[cup3 as usize];
+        next[cup3 as usize] = next[destination as usize];
+        next[destination as usize] = cup1;
+        current = next[current as usize];
+    }
+    next
+}
+
+pub fn part1(order: &[u32], moves: usize) -> i32 {
+    let next = play(order, 0, moves);
+    let mut cup = next[1];
+    let mut labels = 0;
+    while cup != 1 {
+        labels = labels * 10 + cup as i32;
+        cup = next[cup as usize];
+    }
+    labels
+}
+
+pub fn part2(order: &[u32], cups: u32, moves: usize) -> u64 {
+    let next = play(order, cups, moves);
+    let first = next[1];
+    first as u64 * next[first as usize] as u64
+}
+
+pub fn read_order<R: BufRead>(reader: R) -> Result<Vec<u32>, Error> {
+    let (_, line) = parse::lines(reader)
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(Day23::DAY, "missing cup labels"))??;
+
+    let mut order = Vec::new();
+    for (i, ch) in line.char_indices() {
+        let label = &line[i..i + ch.len_utf8()];
+        match ch.to_digit(10) {
+            Some(cup) if cup > 0 && !order.contains(&cup) => order.push(cup),
+            Some(cup) if cup > 0 => {
+                return Err(ParseError::within(Day23::DAY, &line, label, "duplicate cup").into())
+            }
+            _ => {
+                return Err(
+                    ParseError::within(Day23::DAY, &line, label, "expected `1` to `9`").into(),
+                )
+            }
+        }
+    }
+
+    if order.len() < 5 || order.iter().any(|&cup| cup as usize > order.len()) {
+        return Err(
+            ParseError::line(Day23::DAY, &line, "expected cups labelled 1 to N, N >= 5").into(),
+        );
+    }
+    Ok(order)
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "cups",
+    sizes: 5..=9,
+    default_size: 9,
+    build: generate,
+};
+
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let mut cups: Vec<u32> = (1..=size as u32).collect();
+    rng.shuffle(&mut cups);
+    Generated::new(format!(
+        "{}\n",
+        cups.iter().map(u32::to_string).collect::<String>()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let cups = read_order(BufReader::new(File::open("inputs/day23/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&cups, 100), 36472598);
+        assert_eq!(part2(&cups, 1000000, 10000000), 90481418730);
+
+        let example = [3, 8, 9, 1, 2, 5, 4, 6, 7];
+        assert_eq!(part1(&example, 10), 92658374);
+    }
+}
@@ -0,0 +1,320 @@
+use std::{collections::HashSet, io::BufRead};
+
+use crate::{
+    automaton::{Automaton, Hex, Rule},
+    generate::{self, Generated, Generator, Rng},
+    grid::Grid,
+    params, parse,
+    render::{Cell, Frame, Rgb},
+    Answers, Error, Param, Params, ParseError, Solution,
+};
+
+#[derive(Debug)]
+pub enum Step {
+    E,
+    SE,
+    SW,
+    W,
+    NW,
+    NE,
+}
+
+#[derive(Clone, Debug, PartialEq, Hash, Eq)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+
+    type Input = Vec<Vec<Step>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_steps(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input).len()
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        Self::part2_with(input, &Params::default())
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+
+    const PARAMS: &'static [Param] = &[DAYS];
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Self::Answer2 {
+        part2(part1(input), DAYS.get(params))
+    }
+
+    fn stream<R: BufRead>(reader: R, params: &Params) -> Option<Result<Answers<Self>, Error>> {
+        Some(stream(reader, DAYS.get(params)))
+    }
+}
+
+pub const DAYS: Param = Param {
+    name: "days",
+    default: "100",
+    help: "Days of the art exhibit",
+    // The black tiles spread by a tile in every direction each day.
+    check: params::between::<usize, 0, 500>,
+};
+
+/// The tile at the end of `steps` from the reference tile.
+fn tile(steps: &[Step]) -> Point {
+    let (mut small_x, mut small_y) = (0, 0);
+    for step in steps.iter() {
+        // Using doubled coordinates, idea from:
+        // https://www.redblobgames.com/grids/hexagons/#coordinates
+        match step {
+            Step::E => small_x += 2,
+            Step::SE => {
+                small_x += 1;
+                small_y += 1;
+            }
+            Step::SW => {
+                small_x -= 1;
+                small_y += 1;
+            }
+            Step::W => small_x -= 2,
+            Step::NW => {
+                small_x -= 1;
+                small_y -= 1;
+            }
+            Step::NE => {
+                small_x += 1;
+                small_y -= 1;
+            }
+        }
+    }
+    Point::new(small_x, small_y)
+}
+
+fn flip(flipped: &mut HashSet<Point>, p: Point) {
+    if flipped.contains(&p) {
+        flipped.remove(&p);
+    } else {
+        flipped.insert(p);
+    }
+}
+
+pub fn part1(steps: &[Vec<Step>]) -> HashSet<Point> {
+    let mut flipped: HashSet<Point> = HashSet::new();
+    for single_steps in steps.iter() {
+        flip(&mut flipped, tile(single_steps));
+    }
+    flipped
+}
+
+/// The floor as an automaton with black tiles alive. A black tile with no
+/// or more than two black tiles around turns white, a white tile with
+/// exactly two black tiles around turns black.
+pub fn automaton(black: &HashSet<Point>) -> Automaton<Hex> {
+    let black = black.iter().map(|tile| (tile.x, tile.y));
+    Automaton::new(Hex, Rule::new(vec![2], vec![1, 2]), black)
+}
+
+/// Corners of the area a frame shows, as the smallest and largest `(x, y)`.
+type Bounds = ((i32, i32), (i32, i32));
+
+/// Black tiles on white ones within `bounds`. Every tile is two characters
+/// wide, so rows of doubled coordinates are shifted by half a tile against
+/// each other.
+pub fn frame(black: &HashSet<(i32, i32)>, ((min_x, min_y), (max_x, max_y)): Bounds) -> Frame {
+    let (width, height) = ((max_x - min_x + 2) as usize, (max_y - min_y + 1) as usize);
+    Grid::from_fn(width, height, |(x, y)| {
+        let (x, y) = (x as i32 + min_x, y as i32 + min_y);
+        let tile = (x - (x + y).rem_euclid(2), y);
+        if black.contains(&tile) {
+            Cell::new('#', Rgb(30, 30, 30))
+        } else {
+            Cell::new('.', Rgb(230, 230, 230))
+        }
+    })
+}
+
+/// The floor after following the steps for part 1, then after every day of
+/// the exhibit for part 2.
+pub fn animation(
+    steps: &[Vec<Step>],
+    part: u8,
+    params: &Params,
+) -> Box<dyn Iterator<Item = Frame>> {
+    let days = if part == 1 { 0 } else { DAYS.get(params) };
+    let black = part1(steps);
+
+    // The bounds of all days together keep the floor in place.
+    let mut floor = automaton(&black);
+    let mut bounds = ((0, 0), (0, 0));
+    for day in 0..=days {
+        for (x, y) in floor.live() {
+            let ((min_x, min_y), (max_x, max_y)) = bounds;
+            bounds = ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)));
+        }
+        if day < days {
+            floor.step();
+        }
+    }
+
+    let floor = automaton(&black);
+    Box::new(floor.animate(Some(days), move |floor| {
+        frame(&floor.live().collect(), bounds)
+    }))
+}
+
+pub fn part2(black: HashSet<Point>, days: usize) -> usize {
+    let mut floor = automaton(&black);
+    floor.run(days);
+    floor.population()
+}
+
+/// The black tiles of both parts, flipping each tile as its line is read.
+pub fn stream<R: BufRead>(reader: R, days: usize) -> Result<(usize, usize), Error> {
+    let mut flipped: HashSet<Point> = HashSet::new();
+    for line in parse::lines(reader) {
+        let (num, line) = line?;
+        flip(
+            &mut flipped,
+            tile(&read_line(&line).map_err(|e| e.on_line(num))?),
+        );
+    }
+    Ok((flipped.len(), part2(flipped, days)))
+}
+
+/// The steps of a line. An empty line leads to the reference tile.
+fn read_line(line: &str) -> Result<Vec<Step>, ParseError> {
+    let error = |i: usize, reason| ParseError::within(Day24::DAY, line, &line[i..], reason);
+
+    let mut steps: Vec<Step> = Vec::new();
+    let mut last: Option<(usize, char)> = None;
+    for (i, ch) in line.char_indices() {
+        match last.take() {
+            None => match ch {
+                'e' => steps.push(Step::E),
+                'w' => steps.push(Step::W),
+                's' | 'n' => last = Some((i, ch)),
+                _ => return Err(error(i, "expected `e`, `se`, `sw`, `w`, `nw` or `ne`")),
+            },
+            Some((start, pre)) => match ch {
+                'e' if pre == 's' => steps.push(Step::SE),
+                'e' if pre == 'n' => steps.push(Step::NE),
+                'w' if pre == 'n' => steps.push(Step::NW),
+                'w' if pre == 's' => steps.push(Step::SW),
+                _ => return Err(error(start, "expected `e` or `w` after `n` and `s`")),
+            },
+        }
+    }
+    if let Some((start, _)) = last {
+        return Err(error(start, "incomplete direction"));
+    }
+    Ok(steps)
+}
+
+pub fn read_steps<R: BufRead>(reader: R) -> Result<Vec<Vec<Step>>, Error> {
+    let mut result = Vec::new();
+    for line in parse::lines(reader) {
+        let (num, line) = line?;
+        result.push(read_line(&line).map_err(|e| e.on_line(num))?);
+    }
+
+    Ok(result)
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "tiles",
+    sizes: 1..=2000,
+    default_size: 550,
+    build: generate,
+};
+
+/// Steps with their moves in doubled coordinates, as in `part1`.
+const STEPS: [(&str, (i32, i32)); 6] = [
+    ("e", (2, 0)),
+    ("se", (1, 1)),
+    ("sw", (-1, 1)),
+    ("w", (-2, 0)),
+    ("nw", (-1, -1)),
+    ("ne", (1, -1)),
+];
+
+/// Random walks, a fifth of them leading to a tile flipped before.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let mut flipped: Vec<(i32, i32)> = Vec::new();
+    let mut lines = Vec::new();
+    for _ in 0..size {
+        let mut steps: Vec<usize> = (0..rng.range(5..=20)).map(|_| rng.index(6)).collect();
+        let end = steps.iter().fold((0, 0), |(x, y), &step| {
+            (x + STEPS[step].1 .0, y + STEPS[step].1 .1)
+        });
+        if !flipped.is_empty() && rng.chance(20) {
+            let target = *rng.choose(&flipped);
+            let (mut dx, mut dy) = (target.0 - end.0, target.1 - end.1);
+            while dy != 0 {
+                let step = match (dx > 0, dy > 0) {
+                    (true, true) => 1,
+                    (false, true) => 2,
+                    (false, false) => 4,
+                    (true, false) => 5,
+                };
+                dx -= STEPS[step].1 .0;
+                dy -= STEPS[step].1 .1;
+                steps.push(step);
+            }
+            while dx != 0 {
+                let step = if dx > 0 { 0 } else { 3 };
+                dx -= STEPS[step].1 .0;
+                steps.push(step);
+            }
+            rng.shuffle(&mut steps);
+            flipped.push(target);
+        } else {
+            flipped.push(end);
+        }
+        lines.push(steps.iter().map(|&step| STEPS[step].0).collect::<String>());
+    }
+
+    let mut black = HashSet::new();
+    for tile in flipped {
+        if !black.insert(tile) {
+            black.remove(&tile);
+        }
+    }
+    Generated::new(generate::lines(lines)).with_answer(1, black.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let steps = read_steps(BufReader::new(File::open("inputs/day24/1.txt").unwrap())).unwrap();
+        let flipped = part1(&steps);
+        assert_eq!(flipped.len(), 375);
+        assert_eq!(part2(flipped, 100), 3937);
+    }
+
+    #[test]
+    fn test_params() {
+        let params = |value: &str| Params::new(Day24::PARAMS, &[value.to_owned()]);
+        assert!(params("days=500").is_ok());
+        assert_eq!(
+            params("days=501").unwrap_err(),
+            "invalid days `501`: expected a usize from 0 to 500"
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use std::io::BufRead;
+
+use crate::{
+    generate::{Generated, Generator, Rng},
+    math, params, parse, Error, NoAnswer, Param, Params, ParseError, Solution,
+};
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const PARTS: u8 = 1;
+
+    type Input = (u64, u64);
+    type Answer1 = u64;
+    type Answer2 = NoAnswer;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_keys(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        Self::part1_with(input, &Params::default())
+    }
+
+    fn part2(_input: &Self::Input) -> Self::Answer2 {
+        NoAnswer
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+
+    const PARAMS: &'static [Param] = &[MODULUS, SUBJECT];
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Self::Answer1 {
+        part1(input.0, input.1, SUBJECT.get(params), MODULUS.get(params))
+    }
+}
+
+pub const MODULUS: Param = Param {
+    name: "modulus",
+    default: "20201227",
+    help: "Modulus of the handshake transformation",
+    // Finding a loop size takes a table of about the modulus' square root.
+    check: params::between::<u64, 2, { 1 << 40 }>,
+};
+
+pub const SUBJECT: Param = Param {
+    name: "subject",
+    default: "7",
+    help: "Subject number the public keys are transformed from",
+    check: params::number::<u64>,
+};
+
+fn calculate_loop_size(subject: u64, expected: u64, modulus: u64) -> u64 {
+    math::discrete_log(subject, expected, modulus).expect("public key is never reached")
+}
+
+fn calculate_encryption_key(subject: u64, loop_size: u64, modulus: u64) -> u64 {
+    math::powmod(subject, loop_size, modulus)
+}
+
+pub fn part1(card_pubkey: u64, door_pubkey: u64, subject: u64, modulus: u64) -> u64 {
+    assert!(
+        card_pubkey < modulus && door_pubkey < modulus,
+        "public keys have to be below the modulus {}",
+        modulus
+    );
+    let loop_size = calculate_loop_size(subject, card_pubkey, modulus);
+    calculate_encryption_key(door_pubkey, loop_size, modulus)
+}
+
+pub fn read_keys<R: BufRead>(reader: R) -> Result<(u64, u64), Error> {
+    let mut nums = Vec::new();
+    for line in parse::lines(reader) {
+        let (num, line) = line?;
+        if line.is_empty() {
+            continue;
+        }
+
+        let key: u64 = parse::number(Day25::DAY, &line, &line).map_err(|e| e.on_line(num))?;
+        if key == 0 {
+            return Err(
+                ParseError::line(Day25::DAY, &line, "expected a positive key")
+                    .on_line(num)
+                    .into(),
+            );
+        }
+        nums.push(key);
+    }
+
+    match nums[..] {
+        [card, door] => Ok((card, door)),
+        _ => Err(ParseError::end_of_input(Day25::DAY, "expected two public keys").into()),
+    }
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "key pairs",
+    sizes: 1..=1,
+    default_size: 1,
+    build: generate,
+};
+
+/// Public keys of random loop sizes.
+fn generate(rng: &mut Rng, _size: usize) -> Generated {
+    let (subject, modulus): (u64, u64) = (
+        SUBJECT.get(&Params::default()),
+        MODULUS.get(&Params::default()),
+    );
+    let card = rng.range(1..=modulus as i64 - 2) as u64;
+    let door = rng.range(1..=modulus as i64 - 2) as u64;
+    let (card_key, door_key) = (
+        calculate_encryption_key(subject, card, modulus),
+        calculate_encryption_key(subject, door, modulus),
+    );
+    Generated::new(format!("{}\n{}\n", card_key, door_key))
+        .with_answer(1, calculate_encryption_key(door_key, card, modulus))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let (card_pubkey, door_pubkey) =
+            read_keys(BufReader::new(File::open("inputs/day25/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(card_pubkey, door_pubkey, 7, 20201227), 9620012);
+    }
+}
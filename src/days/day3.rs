@@ -0,0 +1,326 @@
+use std::{collections::HashSet, fmt, io::BufRead, iter, ops::RangeInclusive, str::FromStr};
+
+use itertools::{Itertools, MinMaxResult};
+
+use crate::{
+    generate::{Generated, Generator, Rng},
+    grid::Grid,
+    render::{Cell, Frame, Rgb},
+    Error, Param, Params, Solution,
+};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Grid<char>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        load_map(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+
+    const PARAMS: &'static [Param] = &[PART1_SLOPE, PART2_SLOPES];
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Self::Answer1 {
+        trees(input, PART1_SLOPE.get(params))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Self::Answer2 {
+        let slopes: Slopes = PART2_SLOPES.get(params);
+        evaluate(input, &slopes.0)
+            .iter()
+            .map(|&(_, trees)| trees)
+            .product()
+    }
+}
+
+pub const PART1_SLOPE: Param = Param {
+    name: "part1-slope",
+    default: "3/1",
+    help: "Slope of part 1, as `right/down`",
+    check: |value| value.parse::<Slope>().map(|_| ()),
+};
+
+pub const PART2_SLOPES: Param = Param {
+    name: "part2-slopes",
+    default: "1/1,3/1,5/1,7/1,1/2",
+    help: "Slopes whose trees part 2 multiplies, as `right/down`",
+    check: |value| value.parse::<Slopes>().map(|_| ()),
+};
+
+/// Squares the toboggan moves right, or left if negative, and down between
+/// stops, e.g. `-1/2` for a column left every two rows. A slope is kept as
+/// given, `2/2` stopping at every other row unlike `1/1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Slope {
+    pub right: isize,
+    pub down: usize,
+}
+
+impl Slope {
+    /// Panics if `down` is 0, which would never reach the bottom.
+    pub const fn new(right: isize, down: usize) -> Self {
+        assert!(down > 0, "a slope has to go down");
+        Slope { right, down }
+    }
+}
+
+impl FromStr for Slope {
+    type Err = String;
+
+    /// Parses `right/down`, or just `right` for a row down.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (right, down) = s.split_once('/').unwrap_or((s, "1"));
+        match (right.parse(), down.parse()) {
+            (Ok(right), Ok(down)) if down > 0 => Ok(Slope::new(right, down)),
+            _ => Err(format!(
+                "expected a slope `right/down` with down above 0, got `{}`",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Slope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.right, self.down)
+    }
+}
+
+/// Slopes separated by commas.
+pub struct Slopes(pub Vec<Slope>);
+
+impl FromStr for Slopes {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map(Slopes)
+    }
+}
+
+/// Slopes of part 2.
+const SLOPES: [Slope; 5] = [
+    Slope::new(1, 1),
+    Slope::new(3, 1),
+    Slope::new(5, 1),
+    Slope::new(7, 1),
+    Slope::new(1, 2),
+];
+
+pub fn part1(map: &Grid<char>) -> i64 {
+    trees(map, Slope::new(3, 1))
+}
+
+pub fn part2(map: &Grid<char>) -> i64 {
+    SLOPES.iter().map(|&slope| trees(map, slope)).product()
+}
+
+/// Every stop of the toboggan from the top left corner to the bottom of the
+/// map, as `(x, y)`. `x` goes on past the edges of the map, which repeats
+/// to both sides, saturating at the bounds of `isize`.
+pub fn path(map: &Grid<char>, slope: Slope) -> Vec<(isize, usize)> {
+    (0..)
+        .map(|step: usize| {
+            (
+                (step as isize).saturating_mul(slope.right),
+                step.saturating_mul(slope.down),
+            )
+        })
+        .take_while(|&(_, y)| y < map.height())
+        .collect()
+}
+
+/// The squares of the map the toboggan stops at, the same as [`path`] wrapped
+/// into the map. Moving right by whole map widths ends up on the same
+/// squares, so only what is left of them counts.
+fn stops(map: &Grid<char>, slope: Slope) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let width = map.width().max(1);
+    let right = slope.right.rem_euclid(width as isize) as usize;
+    (0..)
+        .map(move |step: usize| (step * right % width, step.saturating_mul(slope.down)))
+        .take_while(move |&(_, y)| y < map.height())
+}
+
+fn is_tree(map: &Grid<char>, (x, y): (isize, usize)) -> bool {
+    *map.get_wrapping(x, y as isize) == '#'
+}
+
+/// Trees the toboggan hits on its way down, after starting off.
+pub fn trees(map: &Grid<char>, slope: Slope) -> i64 {
+    stops(map, slope)
+        .skip(1)
+        .filter(|&stop| map[stop] == '#')
+        .count() as i64
+}
+
+/// The trees hit on each of the slopes.
+pub fn evaluate(map: &Grid<char>, slopes: &[Slope]) -> Vec<(Slope, i64)> {
+    slopes
+        .iter()
+        .map(|&slope| (slope, trees(map, slope)))
+        .collect()
+}
+
+/// Every slope with `right` and `down` in the ranges.
+pub fn slopes(
+    right: RangeInclusive<isize>,
+    down: RangeInclusive<usize>,
+) -> impl Iterator<Item = Slope> {
+    right
+        .cartesian_product(down.filter(|&down| down > 0))
+        .map(|(right, down)| Slope::new(right, down))
+}
+
+/// The slopes hitting the fewest and the most trees, with their trees, the
+/// first of them on ties. `None` without any slopes.
+pub fn extremes(
+    map: &Grid<char>,
+    slopes: impl IntoIterator<Item = Slope>,
+) -> Option<((Slope, i64), (Slope, i64))> {
+    let mut evaluated = slopes.into_iter().map(|slope| (slope, trees(map, slope)));
+    let first = evaluated.next()?;
+    Some(evaluated.fold((first, first), |(fewest, most), next| {
+        (
+            if next.1 < fewest.1 { next } else { fewest },
+            if next.1 > most.1 { next } else { most },
+        )
+    }))
+}
+
+/// The map repeated to both sides as far as the toboggan goes on any of the
+/// `slopes`. Squares it stops at are yellow, trees it hits red, others green.
+pub fn frame(map: &Grid<char>, slopes: &[Slope]) -> Frame {
+    let stops: HashSet<(isize, usize)> =
+        slopes.iter().flat_map(|&slope| path(map, slope)).collect();
+    let width = map.width() as isize;
+    let repeats = stops.iter().map(|&(x, _)| x.div_euclid(width));
+    let (first, last) = match repeats.minmax() {
+        MinMaxResult::NoElements => (0, 0),
+        MinMaxResult::OneElement(repeat) => (repeat.min(0), repeat.max(0)),
+        MinMaxResult::MinMax(first, last) => (first.min(0), last.max(0)),
+    };
+
+    let columns = (last - first + 1) as usize * map.width();
+    Grid::from_fn(columns, map.height(), |(x, y)| {
+        let stop = (x as isize + first * width, y);
+        match (stops.contains(&stop), is_tree(map, stop)) {
+            (true, true) => Cell::new('X', Rgb(220, 40, 40)),
+            (true, false) => Cell::new('O', Rgb(250, 210, 50)),
+            (false, true) => Cell::new('#', Rgb(30, 120, 50)),
+            (false, false) => Cell::new('.', Rgb(235, 240, 245)),
+        }
+    })
+}
+
+/// The path of part 1, or all the paths of part 2.
+pub fn animation(map: &Grid<char>, part: u8, params: &Params) -> Box<dyn Iterator<Item = Frame>> {
+    let slopes = if part == 1 {
+        vec![PART1_SLOPE.get(params)]
+    } else {
+        PART2_SLOPES.get::<Slopes>(params).0
+    };
+    Box::new(iter::once(frame(map, &slopes)))
+}
+
+pub fn load_map<R: BufRead>(reader: R) -> Result<Grid<char>, Error> {
+    Grid::parse(
+        Day3::DAY,
+        reader,
+        |ch| Some(ch).filter(|ch| matches!(ch, '.' | '#')),
+        "expected `.` or `#`",
+    )
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "rows",
+    sizes: 2..=2000,
+    default_size: 323,
+    build: generate,
+};
+
+/// A map 31 squares wide with a quarter of them trees.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let map = Grid::from_fn(31, size, |_| if rng.chance(25) { '#' } else { '.' });
+    let trees = |(right, down): (usize, usize)| {
+        (1..)
+            .map(|step| (step * right % map.width(), step * down))
+            .take_while(|&(_, y)| y < map.height())
+            .filter(|&position| map[position] == '#')
+            .count() as i64
+    };
+    Generated::new(format!("{}\n", map))
+        .with_answer(1, trees((3, 1)))
+        .with_answer(
+            2,
+            SLOPES
+                .iter()
+                .map(|slope| trees((slope.right as usize, slope.down)))
+                .product::<i64>(),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let map = load_map(BufReader::new(File::open("inputs/day3/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&map), 145);
+        assert_eq!(part2(&map), 3424528800);
+    }
+
+    #[test]
+    fn test_slopes() {
+        let map: Grid<char> = load_map("..#\n#..\n.#.\n#.#\n".as_bytes()).unwrap();
+        let left = "-1/1".parse().unwrap();
+        assert_eq!(path(&map, left), [(0, 0), (-1, 1), (-2, 2), (-3, 3)]);
+        assert_eq!(trees(&map, left), 2);
+        assert_eq!(trees(&map, Slope::new(1, 2)), 1);
+        assert_eq!(
+            evaluate(&map, &"0/1,3".parse::<Slopes>().unwrap().0),
+            [(Slope::new(0, 1), 2), (Slope::new(3, 1), 2)]
+        );
+        assert_eq!(
+            extremes(&map, slopes(-1..=1, 0..=2)),
+            Some(((Slope::new(-1, 2), 0), (Slope::new(-1, 1), 2)))
+        );
+        assert_eq!(
+            path(&map, Slope::new(4, 1)),
+            [(0, 0), (4, 1), (8, 2), (12, 3)]
+        );
+        assert_eq!(path(&map, Slope::new(isize::MIN, 1))[3], (isize::MIN, 3));
+        assert_eq!(path(&map, Slope::new(1, usize::MAX)), [(0, 0)]);
+        assert_eq!(
+            trees(&map, Slope::new(isize::MAX, 1)),
+            trees(&map, Slope::new(1, 1))
+        );
+        assert!("1/0".parse::<Slope>().is_err());
+        let twice: Slope = "2/2".parse().unwrap();
+        assert_ne!(twice, Slope::new(1, 1));
+        assert_eq!(twice.to_string(), "2/2");
+        assert_eq!(path(&map, twice), [(0, 0), (2, 2)]);
+        assert_eq!(slopes(0..=2, 1..=2).count(), 6);
+        assert_eq!(frame(&map, &[left]).width(), 6);
+        let params = Params::new(Day3::PARAMS, &["part1-slope=-1/1".to_owned()]).unwrap();
+        let drawn = animation(&map, 1, &params).next().unwrap();
+        assert_eq!(drawn.width(), 6);
+    }
+}
@@ -0,0 +1,356 @@
+use std::{fmt, io::BufRead, str::FromStr};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use simple_error::{require_with, SimpleError};
+
+use crate::{
+    generate::{Generated, Generator, Rng},
+    parse, Error, ParseError, Solution,
+};
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Passport {
+    birth_year: Option<u32>,
+    issue_year: Option<u32>,
+    expiration_year: Option<u32>,
+    height: Option<String>,
+    hair_colour: Option<String>,
+    eye_colour: Option<String>,
+    passport_id: Option<String>,
+    country_id: Option<u32>,
+}
+
+impl Passport {
+    /// Adds the space separated `key:value` fields of a single line.
+    pub fn add_fields(&mut self, line: &str) -> Result<(), ParseError> {
+        for field in line.split_whitespace() {
+            let (key, value) = field.split_once(':').ok_or_else(|| {
+                ParseError::within(Day4::DAY, line, field, "expected `key:value`")
+            })?;
+            match key {
+                "byr" => self.birth_year = Some(parse::number(Day4::DAY, line, value)?),
+                "iyr" => self.issue_year = Some(parse::number(Day4::DAY, line, value)?),
+                "eyr" => self.expiration_year = Some(parse::number(Day4::DAY, line, value)?),
+                "hgt" => self.height = Some(value.to_owned()),
+                "hcl" => self.hair_colour = Some(value.to_owned()),
+                "ecl" => self.eye_colour = Some(value.to_owned()),
+                "pid" => self.passport_id = Some(value.to_owned()),
+                "cid" => self.country_id = Some(parse::number(Day4::DAY, line, value)?),
+                _ => return Err(ParseError::within(Day4::DAY, line, key, "unknown field")),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn has_all_required_fields(&self) -> bool {
+        self.birth_year.is_some()
+            && self.issue_year.is_some()
+            && self.expiration_year.is_some()
+            && self.height.is_some()
+            && self.hair_colour.is_some()
+            && self.eye_colour.is_some()
+            && self.passport_id.is_some()
+    }
+
+    pub fn validate(&self) -> Result<(), SimpleError> {
+        if require_with!(self.birth_year, "no byr") < 1920
+            || require_with!(self.birth_year, "no byr") > 2002
+        {
+            return Err(SimpleError::new("Invalid byr"));
+        }
+
+        if require_with!(self.issue_year, "no iyr") < 2010
+            || require_with!(self.issue_year, "no iyr") > 2020
+        {
+            return Err(SimpleError::new("Invalid iyr"));
+        }
+
+        if require_with!(self.expiration_year, "no eyr") < 2020
+            || require_with!(self.expiration_year, "no eyr") > 2030
+        {
+            return Err(SimpleError::new("Invalid eyr"));
+        }
+
+        lazy_static! {
+            static ref HEIGHT_RE: Regex = Regex::new(r"^(\d+)(cm|in)$").unwrap();
+        }
+        let height_cap = require_with!(
+            HEIGHT_RE.captures(require_with!(self.height.as_deref(), "no height")),
+            "fiasco height"
+        );
+        let height_value = height_cap[1]
+            .parse::<u32>()
+            .map_err(|_| SimpleError::new("invalid height value"))?;
+        match &height_cap[2] {
+            "cm" => {
+                if !(150..=193).contains(&height_value) {
+                    return Err(SimpleError::new("Invalid height"));
+                }
+            }
+            "in" => {
+                if !(59..=76).contains(&height_value) {
+                    return Err(SimpleError::new("Invalid height"));
+                }
+            }
+            _ => return Err(SimpleError::new("Invalid height unit")),
+        };
+
+        lazy_static! {
+            static ref HAIR_COLOUR_RE: Regex = Regex::new(r"^#[a-f0-9]{6}$").unwrap();
+        }
+        if !HAIR_COLOUR_RE.is_match(require_with!(self.hair_colour.as_deref(), "no hair colour")) {
+            return Err(SimpleError::new("Invalid hair colour"));
+        }
+
+        lazy_static! {
+            static ref EYE_COLOUR_RE: Regex =
+                Regex::new(r"^(amb|blu|brn|gry|grn|hzl|oth)$").unwrap();
+        }
+        if !EYE_COLOUR_RE.is_match(require_with!(self.eye_colour.as_deref(), "no eye colour")) {
+            return Err(SimpleError::new("Invalid eye colour"));
+        }
+
+        lazy_static! {
+            static ref PID_RE: Regex = Regex::new(r"^\d{9}$").unwrap();
+        }
+        if !PID_RE.is_match(require_with!(self.passport_id.as_deref(), "no passport id")) {
+            return Err(SimpleError::new("Invalid passport id"));
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Passport {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut passport = Passport::default();
+        for (num, line) in s.lines().enumerate() {
+            passport.add_fields(line).map_err(|e| e.on_line(num + 1))?;
+        }
+
+        Ok(passport)
+    }
+}
+
+/// The fields present on one line, in the order of the puzzle description.
+impl fmt::Display for Passport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let number = |value: Option<u32>| value.map(|value| value.to_string());
+        let fields = [
+            ("byr", number(self.birth_year)),
+            ("iyr", number(self.issue_year)),
+            ("eyr", number(self.expiration_year)),
+            ("hgt", self.height.clone()),
+            ("hcl", self.hair_colour.clone()),
+            ("ecl", self.eye_colour.clone()),
+            ("pid", self.passport_id.clone()),
+            ("cid", number(self.country_id)),
+        ];
+        let line = fields
+            .iter()
+            .filter_map(|(key, value)| value.as_ref().map(|value| format!("{}:{}", key, value)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        write!(f, "{}", line)
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Passport>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_passports(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+}
+
+pub fn part1(passports: &[Passport]) -> usize {
+    passports
+        .iter()
+        .filter(|passport| passport.has_all_required_fields())
+        .count()
+}
+pub fn part2(passports: &[Passport]) -> usize {
+    passports
+        .iter()
+        .filter(|passport| passport.validate().is_ok())
+        .count()
+}
+
+pub fn read_passports<R: BufRead>(reader: R) -> Result<Vec<Passport>, Error> {
+    let mut result = Vec::new();
+    let mut current: Option<Passport> = None;
+
+    for line in parse::lines(reader) {
+        let (num, line) = line?;
+        if line.is_empty() {
+            result.extend(current.take());
+        } else {
+            current
+                .get_or_insert_with(Passport::default)
+                .add_fields(&line)
+                .map_err(|e| e.on_line(num))?;
+        }
+    }
+    result.extend(current);
+
+    Ok(result)
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "passports",
+    sizes: 1..=2000,
+    default_size: 290,
+    build: generate,
+};
+
+const EYE_COLOURS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+/// Value of a required field, within the rules or breaking them.
+fn field(rng: &mut Rng, key: &str, valid: bool) -> String {
+    let digits = |rng: &mut Rng, len| -> String {
+        (0..len)
+            .map(|_| char::from(b'0' + rng.index(10) as u8))
+            .collect()
+    };
+    let hex = |rng: &mut Rng| -> String {
+        (0..6)
+            .map(|_| *rng.choose(b"0123456789abcdef") as char)
+            .collect()
+    };
+    let year = |rng: &mut Rng, low: i64, high: i64| {
+        if valid {
+            rng.range(low..=high)
+        } else if rng.chance(50) {
+            rng.range(low - 20..=low - 1)
+        } else {
+            rng.range(high + 1..=high + 20)
+        }
+        .to_string()
+    };
+
+    match (key, valid) {
+        ("byr", _) => year(rng, 1920, 2002),
+        ("iyr", _) => year(rng, 2010, 2020),
+        ("eyr", _) => year(rng, 2020, 2030),
+        ("hgt", true) if rng.chance(50) => format!("{}cm", rng.range(150..=193)),
+        ("hgt", true) => format!("{}in", rng.range(59..=76)),
+        ("hgt", false) => match rng.index(3) {
+            0 => format!("{}cm", rng.range(194..=250)),
+            1 => format!("{}in", rng.range(40..=58)),
+            _ => rng.range(59..=193).to_string(),
+        },
+        ("hcl", true) => format!("#{}", hex(rng)),
+        ("hcl", false) if rng.chance(50) => hex(rng),
+        ("hcl", false) => format!("#{}z", &hex(rng)[1..]),
+        ("ecl", true) => rng.choose(&EYE_COLOURS).to_string(),
+        ("ecl", false) => rng.choose(&["red", "xry", "gmt", "zzz"]).to_string(),
+        ("pid", true) => digits(rng, 9),
+        ("pid", false) => {
+            let len = *rng.choose(&[8, 10]);
+            digits(rng, len)
+        }
+        _ => unreachable!("not a required field"),
+    }
+}
+
+/// Valid passports, passports missing a required field and passports with
+/// every field but one of them invalid.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let required = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+    let (mut complete, mut valid) = (0, 0);
+    let mut passports = Vec::new();
+    for _ in 0..size {
+        let (missing, invalid) = match rng.index(3) {
+            0 => (None, None),
+            1 => (Some(rng.index(required.len())), None),
+            _ => (None, Some(rng.index(required.len()))),
+        };
+        complete += usize::from(missing.is_none());
+        valid += usize::from(missing.is_none() && invalid.is_none());
+
+        let mut fields: Vec<String> = required
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| Some(i) != missing)
+            .map(|(i, key)| format!("{}:{}", key, field(rng, key, Some(i) != invalid)))
+            .collect();
+        if rng.chance(50) {
+            fields.push(format!("cid:{}", rng.range(100..=350)));
+        }
+        rng.shuffle(&mut fields);
+
+        let mut lines = vec![Vec::new()];
+        for field in fields {
+            if !lines.last().unwrap().is_empty() && rng.chance(25) {
+                lines.push(Vec::new());
+            }
+            lines.last_mut().unwrap().push(field);
+        }
+        passports.push(
+            lines
+                .iter()
+                .map(|line| line.join(" "))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+    }
+    Generated::new(passports.join("\n\n") + "\n")
+        .with_answer(1, complete)
+        .with_answer(2, valid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let passports =
+            read_passports(BufReader::new(File::open("inputs/day4/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&passports), 219);
+        assert_eq!(part2(&passports), 127);
+    }
+
+    proptest! {
+        #[test]
+        fn passport_round_trips(
+            years in prop::array::uniform4(prop::option::of(any::<u32>())),
+            texts in prop::array::uniform4(prop::option::of("[a-z0-9#]{1,9}")),
+        ) {
+            let [birth_year, issue_year, expiration_year, country_id] = years;
+            let [height, hair_colour, eye_colour, passport_id] = texts;
+            let passport = Passport {
+                birth_year,
+                issue_year,
+                expiration_year,
+                height,
+                hair_colour,
+                eye_colour,
+                passport_id,
+                country_id,
+            };
+            prop_assert_eq!(passport.to_string().parse::<Passport>().unwrap(), passport);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use std::{fmt, io::BufRead, str::FromStr};
+
+use itertools::Itertools;
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    parse, Answers, Error, Params, ParseError, Solution,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Seat {
+    row: usize,
+    column: usize,
+}
+
+impl Seat {
+    pub fn generate_id(&self) -> usize {
+        self.row * 8 + self.column
+    }
+}
+
+impl FromStr for Seat {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.chars().count() != 10 {
+            return Err(ParseError::line(Day5::DAY, s, "expected 10 characters"));
+        }
+
+        let (mut row, mut column) = (0, 0);
+        for (pos, (i, ch)) in s.char_indices().enumerate() {
+            match ch {
+                'F' if pos < 7 => {
+                    row <<= 1;
+                }
+                'B' if pos < 7 => {
+                    row <<= 1;
+                    row |= 1;
+                }
+                'L' if pos >= 7 => {
+                    column <<= 1;
+                }
+                'R' if pos >= 7 => {
+                    column <<= 1;
+                    column |= 1;
+                }
+                _ => {
+                    let expected = if pos < 7 { "`F` or `B`" } else { "`L` or `R`" };
+                    return Err(ParseError::within(
+                        Day5::DAY,
+                        s,
+                        &s[i..i + ch.len_utf8()],
+                        format!("expected {}", expected),
+                    ));
+                }
+            }
+        }
+
+        Ok(Seat { row, column })
+    }
+}
+
+/// The boarding pass, e.g. `FBFBBFFRLR`.
+impl fmt::Display for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bit in (0..7).rev() {
+            write!(f, "{}", if self.row >> bit & 1 == 1 { 'B' } else { 'F' })?;
+        }
+        for bit in (0..3).rev() {
+            write!(
+                f,
+                "{}",
+                if self.column >> bit & 1 == 1 {
+                    'R'
+                } else {
+                    'L'
+                }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Vec<Seat>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_seats(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+
+    fn stream<R: BufRead>(reader: R, _params: &Params) -> Option<Result<Answers<Self>, Error>> {
+        Some(stream(reader))
+    }
+}
+
+pub fn part1(seats: &[Seat]) -> usize {
+    seats.iter().map(|seat| seat.generate_id()).max().unwrap()
+}
+
+pub fn part2(seats: &[Seat]) -> usize {
+    seats
+        .iter()
+        .map(|seat| seat.generate_id())
+        .sorted()
+        .tuple_windows()
+        .find(|(x, y)| *x != y - 1)
+        .unwrap()
+        .0
+        + 1
+}
+
+/// Both answers from a map of the taken seats, there being only 1024 IDs.
+pub fn stream<R: BufRead>(reader: R) -> Result<(usize, usize), Error> {
+    let mut taken = [false; 128 * 8];
+    for seat in parse::records(reader, Seat::from_str) {
+        taken[seat?.generate_id()] = true;
+    }
+    let first = taken.iter().position(|&taken| taken).unwrap();
+    let last = taken.iter().rposition(|&taken| taken).unwrap();
+    let free = (first..last).find(|&id| !taken[id]).unwrap();
+    Ok((last, free))
+}
+
+pub fn read_seats<R: BufRead>(reader: R) -> Result<Vec<Seat>, Error> {
+    parse::records(reader, Seat::from_str).collect()
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "seats",
+    sizes: 3..=1000,
+    default_size: 800,
+    build: generate,
+};
+
+/// Consecutive seat IDs with one missing in between.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let first = rng.range(0..=(1023 - size) as i64) as usize;
+    let last = first + size;
+    let missing = rng.range(first as i64 + 1..=last as i64 - 1) as usize;
+    let mut seats: Vec<_> = (first..=last)
+        .filter(|&id| id != missing)
+        .map(|id| Seat {
+            row: id / 8,
+            column: id % 8,
+        })
+        .collect();
+    rng.shuffle(&mut seats);
+    Generated::new(generate::lines(seats))
+        .with_answer(1, last)
+        .with_answer(2, missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let seats = read_seats(BufReader::new(File::open("inputs/day5/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&seats), 955);
+        assert_eq!(part2(&seats), 569);
+    }
+
+    proptest! {
+        #[test]
+        fn seat_is_binary_row_and_column(row in 0..128usize, column in 0..8usize) {
+            let pass: String = format!("{:07b}{:03b}", row, column)
+                .chars()
+                .enumerate()
+                .map(|(i, bit)| match (i < 7, bit) {
+                    (true, '0') => 'F',
+                    (true, _) => 'B',
+                    (false, '0') => 'L',
+                    (false, _) => 'R',
+                })
+                .collect();
+            let seat: Seat = pass.parse().unwrap();
+            prop_assert_eq!(seat, Seat { row, column });
+            prop_assert_eq!(seat.generate_id(), row * 8 + column);
+            prop_assert_eq!(seat.to_string(), pass);
+        }
+    }
+}
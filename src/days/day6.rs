@@ -0,0 +1,153 @@
+use std::{collections::HashSet, io::BufRead};
+
+use itertools::Itertools;
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    parse, Error, ParseError, Solution,
+};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_answers(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+}
+
+pub fn part1(input: &[String]) -> usize {
+    let mut group: String = String::new();
+    let mut result = 0;
+    for (num, line) in input.iter().enumerate() {
+        if line.is_empty() {
+            result += group.chars().unique().count();
+            group.clear();
+        } else if num == input.len() - 1 {
+            group += line;
+            result += group.chars().unique().count();
+            group.clear();
+        } else {
+            group += line;
+        }
+    }
+
+    result
+}
+pub fn part2(input: &[String]) -> usize {
+    let mut set: HashSet<char> = HashSet::new();
+    let mut result = 0;
+    let mut is_new_group = true;
+
+    for (num, line) in input.iter().enumerate() {
+        if line.is_empty() {
+            result += set.len();
+            set.clear();
+            is_new_group = true;
+        } else if num == input.len() - 1 {
+            result += if is_new_group {
+                line.chars().collect::<HashSet<_>>().len()
+            } else {
+                set.intersection(&line.chars().collect())
+                    .cloned()
+                    .collect::<HashSet<_>>()
+                    .len()
+            };
+        } else {
+            set = if is_new_group {
+                is_new_group = false;
+                line.chars().collect()
+            } else {
+                set.intersection(&line.chars().collect()).cloned().collect()
+            };
+        }
+    }
+
+    result
+}
+
+pub fn read_answers<R: BufRead>(reader: R) -> Result<Vec<String>, Error> {
+    let mut answers = Vec::new();
+    for line in parse::lines(reader) {
+        let (num, line) = line?;
+        if let Some((i, ch)) = line.char_indices().find(|(_, ch)| !ch.is_ascii_lowercase()) {
+            let question = &line[i..i + ch.len_utf8()];
+            return Err(
+                ParseError::within(Day6::DAY, &line, question, "expected `a` to `z`")
+                    .on_line(num)
+                    .into(),
+            );
+        }
+        answers.push(line);
+    }
+    Ok(answers)
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "groups",
+    sizes: 1..=2000,
+    default_size: 480,
+    build: generate,
+};
+
+/// Groups of one to five people, who tend to share some of their answers.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let (mut anyone, mut everyone) = (0, 0);
+    let mut groups = Vec::new();
+    for _ in 0..size {
+        let len = rng.index(6);
+        let shared: HashSet<char> = generate::word(rng, len).chars().collect();
+        let people: Vec<HashSet<char>> = (0..rng.range(1..=5))
+            .map(|_| {
+                let mut answers = shared.clone();
+                let len = rng.range(1..=8) as usize;
+                answers.extend(generate::word(rng, len).chars());
+                answers
+            })
+            .collect();
+
+        let all: HashSet<char> = people.iter().flatten().copied().collect();
+        anyone += all.len();
+        everyone += all
+            .iter()
+            .filter(|answer| people.iter().all(|person| person.contains(answer)))
+            .count();
+        groups.push(
+            people
+                .iter()
+                .map(|person| person.iter().sorted().collect::<String>())
+                .join("\n"),
+        );
+    }
+    Generated::new(groups.join("\n\n") + "\n")
+        .with_answer(1, anyone)
+        .with_answer(2, everyone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let input = read_answers(BufReader::new(File::open("inputs/day6/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&input), 6799);
+        assert_eq!(part2(&input), 3354);
+    }
+}
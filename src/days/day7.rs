@@ -0,0 +1,274 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    io::BufRead,
+    str::FromStr,
+};
+
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    params, parse, Error, Param, Params, ParseError, Solution,
+};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Rule {
+    name: String,
+    children: Vec<(String, usize)>,
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref PARENT_RE: Regex = Regex::new(r"^(.*) bags contain (.*)$").unwrap();
+            static ref CHILD_RE: Regex = Regex::new(r"(\d) (\w+ \w+) bags?").unwrap();
+        }
+        let caps = PARENT_RE.captures(s).ok_or_else(|| {
+            ParseError::line(Day7::DAY, s, "expected `<colour> bags contain <bags>`")
+        })?;
+        let name = caps[1].to_owned();
+        let children = CHILD_RE
+            .captures_iter(caps.get(2).map_or("", |rules| rules.as_str()))
+            .map(|child| Ok((child[2].to_owned(), parse::number(Day7::DAY, s, &child[1])?)))
+            .collect::<Result<_, ParseError>>()?;
+        Ok(Rule { name, children })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} bags contain ", self.name)?;
+        if self.children.is_empty() {
+            return write!(f, "no other bags.");
+        }
+        let children = self
+            .children
+            .iter()
+            .map(|(name, count)| {
+                let bags = if *count == 1 { "bag" } else { "bags" };
+                format!("{} {} {}", count, name, bags)
+            })
+            .join(", ");
+        write!(f, "{}.", children)
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<Rule>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_rules(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        Self::part1_with(input, &Params::default())
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        Self::part2_with(input, &Params::default())
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+
+    const PARAMS: &'static [Param] = &[BAG];
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Self::Answer1 {
+        part1(input, &BAG.get::<String>(params))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Self::Answer2 {
+        part2(input, &BAG.get::<String>(params))
+    }
+}
+
+pub const BAG: Param = Param {
+    name: "bag",
+    default: "shiny gold",
+    help: "Colour of the bag to carry",
+    check: params::text,
+};
+
+fn find_bag(rules: &[Rule], name: &str) -> Vec<String> {
+    let mut results = Vec::new();
+    for rule in rules.iter() {
+        for children in rule.children.iter() {
+            if children.0 == name {
+                results.push(rule.name.to_owned());
+                results.extend(find_bag(rules, &rule.name));
+            }
+        }
+    }
+    results
+}
+fn count_bags(rules: &[Rule], name: &str) -> usize {
+    rules
+        .iter()
+        .find(|rule| rule.name == name)
+        .unwrap_or_else(|| panic!("no rule for {} bags", name))
+        .children
+        .iter()
+        .fold(1, |sum, child| sum + child.1 * count_bags(rules, &child.0))
+}
+
+pub fn part1(rules: &[Rule], bag: &str) -> usize {
+    let bags = find_bag(rules, bag);
+    bags.iter().unique().count()
+}
+pub fn part2(rules: &[Rule], bag: &str) -> usize {
+    count_bags(rules, bag) - 1
+}
+
+/// The rules, which can't have a bag end up containing itself.
+pub fn read_rules<R: BufRead>(reader: R) -> Result<Vec<Rule>, Error> {
+    let mut rules = Vec::new();
+    for line in parse::lines(reader) {
+        let (num, line) = line?;
+        if !line.is_empty() {
+            rules.push(Rule::from_str(&line).map_err(|e| e.on_line(num))?);
+        }
+    }
+
+    let children: HashMap<&str, &[(String, usize)]> = rules
+        .iter()
+        .map(|rule| (rule.name.as_str(), rule.children.as_slice()))
+        .collect();
+    let cycle = parse::find_cycle(rules.iter().map(|rule| rule.name.as_str()), |name| {
+        children
+            .get(name)
+            .into_iter()
+            .flat_map(|children| children.iter().map(|(child, _)| child.as_str()))
+    });
+    if let Some(bag) = cycle {
+        return Err(ParseError::end_of_input(
+            Day7::DAY,
+            format!("{} bags end up containing themselves", bag),
+        )
+        .into());
+    }
+    Ok(rules)
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "colours",
+    sizes: 12..=2000,
+    default_size: 594,
+    build: generate,
+};
+
+/// Bags in six levels, each containing only bags of lower levels, so the
+/// rules can't be circular and the shiny gold bag in the middle level holds
+/// a countable number of bags.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    const LEVELS: usize = 6;
+    let mut names = HashSet::new();
+    names.insert("shiny gold".to_owned());
+    while names.len() < size {
+        let (adjective, colour) = (rng.range(3..=7) as usize, rng.range(3..=6) as usize);
+        names.insert(format!(
+            "{} {}",
+            generate::word(rng, adjective),
+            generate::word(rng, colour)
+        ));
+    }
+    let mut names: Vec<String> = names.into_iter().sorted().collect();
+    rng.shuffle(&mut names);
+    let gold = names.iter().position(|name| name == "shiny gold").unwrap();
+    let level_size = size / LEVELS;
+    names.swap(gold, 3 * level_size + rng.index(level_size));
+
+    let mut rules: Vec<Rule> = Vec::new();
+    for (i, name) in names.iter().enumerate() {
+        let level = (i / level_size).min(LEVELS - 1);
+        let mut children: Vec<(String, usize)> = Vec::new();
+        if level > 0 {
+            for _ in 0..rng.range(1..=4) {
+                let child = &names[rng.index(level * level_size)];
+                if children.iter().all(|(name, _)| name != child) {
+                    children.push((child.clone(), rng.range(1..=5) as usize));
+                }
+            }
+        }
+        rules.push(Rule {
+            name: name.clone(),
+            children,
+        });
+    }
+
+    let mut containing = HashSet::new();
+    let mut queue = vec!["shiny gold"];
+    while let Some(bag) = queue.pop() {
+        for rule in &rules {
+            if rule.children.iter().any(|(child, _)| child == bag)
+                && containing.insert(rule.name.as_str())
+            {
+                queue.push(&rule.name);
+            }
+        }
+    }
+    // Rules only refer to bags defined before them.
+    let mut inside: HashMap<&str, usize> = HashMap::new();
+    for rule in &rules {
+        let count = rule
+            .children
+            .iter()
+            .map(|(child, count)| count * (1 + inside[child.as_str()]))
+            .sum();
+        inside.insert(&rule.name, count);
+    }
+
+    let answer1 = containing.len();
+    let answer2 = inside["shiny gold"];
+    rng.shuffle(&mut rules);
+    Generated::new(generate::lines(rules))
+        .with_answer(1, answer1)
+        .with_answer(2, answer2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let rules = read_rules(BufReader::new(File::open("inputs/day7/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&rules, "shiny gold"), 316);
+        assert_eq!(part2(&rules, "shiny gold"), 11310);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let rules = "light red bags contain 1 shiny gold bag.\n\
+                     shiny gold bags contain 1 light red bag.\n";
+        assert_eq!(
+            read_rules(rules.as_bytes()).unwrap_err().to_string(),
+            "day 7, end of input: light red bags end up containing themselves"
+        );
+        let rules = "shiny gold bags contain 2 light red bags.\n\
+                     light red bags contain no other bags.\n";
+        assert!(read_rules(rules.as_bytes()).is_ok());
+    }
+
+    proptest! {
+        #[test]
+        fn rule_round_trips(
+            name in "[a-z]{1,8} [a-z]{1,8}",
+            children in prop::collection::vec(("[a-z]{1,8} [a-z]{1,8}", 1..10usize), 0..5),
+        ) {
+            let rule = Rule { name, children };
+            prop_assert_eq!(rule.to_string().parse::<Rule>().unwrap(), rule);
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use std::{fmt, io::BufRead, str::FromStr};
+
+use simple_error::SimpleError;
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    parse, Error, ParseError, Solution,
+};
+
+#[derive(PartialEq)]
+enum ExecutionStatus {
+    Ok,
+    BootLoop,
+    Terminated,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Instruction {
+    NOP(i64),
+    ACC(i64),
+    JMP(i64),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (operation, argument) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::line(Day8::DAY, s, "expected `<operation> <argument>`"))?;
+        let val: i64 = parse::number(Day8::DAY, s, argument)?;
+        match operation {
+            "nop" => Ok(Instruction::NOP(val)),
+            "acc" => Ok(Instruction::ACC(val)),
+            "jmp" => Ok(Instruction::JMP(val)),
+            _ => Err(ParseError::within(
+                Day8::DAY,
+                s,
+                operation,
+                "unknown instruction",
+            )),
+        }
+    }
+}
+/// The argument always has a sign, e.g. `acc +3`.
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::NOP(val) => write!(f, "nop {:+}", val),
+            Instruction::ACC(val) => write!(f, "acc {:+}", val),
+            Instruction::JMP(val) => write!(f, "jmp {:+}", val),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Program {
+    accumulator: i64,
+    next: usize,
+    code: Vec<Instruction>,
+    executed: Vec<usize>,
+}
+
+impl Program {
+    /// Reads the instructions as the lines come in, without keeping the
+    /// lines themselves.
+    pub fn read<R: BufRead>(reader: R) -> Result<Self, Error> {
+        Ok(Program {
+            accumulator: 0,
+            next: 0,
+            code: parse::lines(reader)
+                .map(|line| {
+                    let (num, line) = line?;
+                    Ok(Instruction::from_str(&line).map_err(|e| e.on_line(num))?)
+                })
+                .collect::<Result<_, Error>>()?,
+            executed: Vec::new(),
+        })
+    }
+
+    fn tick(&mut self) -> ExecutionStatus {
+        if self.executed.contains(&self.next) {
+            return ExecutionStatus::BootLoop;
+        }
+
+        if self.next >= self.code.len() {
+            return ExecutionStatus::Terminated;
+        }
+
+        self.executed.push(self.next);
+        match self.code[self.next] {
+            Instruction::NOP(_) => {
+                self.next += 1;
+            }
+            Instruction::JMP(val) => {
+                self.next = (self.next as i64 + val) as usize;
+            }
+            Instruction::ACC(val) => {
+                self.accumulator += val;
+                self.next += 1;
+            }
+        };
+
+        ExecutionStatus::Ok
+    }
+
+    fn create_mutated(&self, index: usize) -> Result<Self, SimpleError> {
+        match self.code[index] {
+            Instruction::NOP(val) => {
+                let mut clone = self.clone();
+                clone.code[index] = Instruction::JMP(val);
+                Ok(clone)
+            }
+            Instruction::JMP(val) => {
+                let mut clone = self.clone();
+                clone.code[index] = Instruction::NOP(val);
+                Ok(clone)
+            }
+            Instruction::ACC(_) => Err(SimpleError::new("Cannot flip ACC")),
+        }
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = Program;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        Program::read(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input.clone())
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input.clone())
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+}
+
+pub fn part1(mut program: Program) -> i64 {
+    while program.tick() == ExecutionStatus::Ok {}
+    program.accumulator
+}
+pub fn part2(program: Program) -> i64 {
+    let mut terminated = false;
+    let mut line_to_flip = 0;
+    let mut result = 0;
+    while !terminated {
+        if let Ok(mut program) = program.create_mutated(line_to_flip) {
+            loop {
+                match program.tick() {
+                    ExecutionStatus::Ok => (),
+                    ExecutionStatus::BootLoop => {
+                        break;
+                    }
+                    ExecutionStatus::Terminated => {
+                        terminated = true;
+                        result = program.accumulator;
+                        break;
+                    }
+                }
+            }
+        }
+        line_to_flip += 1;
+    }
+    result
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "instructions",
+    sizes: 10..=2000,
+    default_size: 630,
+    build: generate,
+};
+
+/// Runs `code` until it terminates or loops, returns which of them and the
+/// program as it stopped.
+fn run(code: &[Instruction]) -> (ExecutionStatus, Program) {
+    let mut program = Program {
+        accumulator: 0,
+        next: 0,
+        code: code.to_vec(),
+        executed: Vec::new(),
+    };
+    loop {
+        match program.tick() {
+            ExecutionStatus::Ok => {}
+            status => return (status, program),
+        }
+    }
+}
+
+/// A terminating program whose path only jumps forward, corrupted by
+/// turning a `nop` on it into a `jmp` back to an earlier instruction of the
+/// path. Programs where flipping another instruction would terminate as
+/// well are thrown away.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    loop {
+        let offset = |rng: &mut Rng, at: usize| loop {
+            let offset = rng.range(-(at.min(40) as i64)..=(size - 1 - at).min(40) as i64);
+            if offset != 0 {
+                break offset;
+            }
+        };
+        let mut code: Vec<Instruction> = (0..size)
+            .map(|at| match rng.index(3) {
+                0 => Instruction::ACC(rng.range(-50..=50)),
+                1 => Instruction::NOP(offset(rng, at)),
+                _ => Instruction::JMP(offset(rng, at)),
+            })
+            .collect();
+
+        let mut path = Vec::new();
+        let mut at = 0;
+        while at < size {
+            path.push(at);
+            code[at] = match rng.index(20) {
+                0..=10 => Instruction::ACC(rng.range(-50..=50)),
+                11..=14 => Instruction::NOP(offset(rng, at)),
+                _ => Instruction::JMP(rng.range(1..=(size - at).min(6) as i64)),
+            };
+            at = match code[at] {
+                Instruction::JMP(offset) => at + offset as usize,
+                _ => at + 1,
+            };
+        }
+
+        let corruptible: Vec<usize> = path
+            .iter()
+            .skip(1)
+            .copied()
+            .filter(|&at| !matches!(code[at], Instruction::JMP(_)))
+            .collect();
+        if corruptible.is_empty() {
+            continue;
+        }
+        let corrupted = *rng.choose(&corruptible);
+        let before: Vec<usize> = path.iter().copied().filter(|&at| at < corrupted).collect();
+        let back = (corrupted - *rng.choose(&before)) as i64;
+        code[corrupted] = Instruction::NOP(-back);
+        let (_, fixed) = run(&code);
+        code[corrupted] = Instruction::JMP(-back);
+
+        let (status, looping) = run(&code);
+        let unique = status == ExecutionStatus::BootLoop
+            && looping.executed.iter().all(|&at| {
+                at == corrupted
+                    || looping.create_mutated(at).map_or(true, |program| {
+                        run(&program.code).0 == ExecutionStatus::BootLoop
+                    })
+            });
+        if unique {
+            return Generated::new(generate::lines(code))
+                .with_answer(1, looping.accumulator)
+                .with_answer(2, fixed.accumulator);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let program =
+            Program::read(BufReader::new(File::open("inputs/day8/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(program.clone()), 1262);
+        assert_eq!(part2(program), 1643);
+    }
+
+    proptest! {
+        #[test]
+        fn instruction_round_trips(operation in "nop|acc|jmp", val: i64) {
+            let line = format!("{} {:+}", operation, val);
+            let instruction: Instruction = line.parse().unwrap();
+            prop_assert_eq!(instruction.to_string(), line);
+        }
+    }
+}
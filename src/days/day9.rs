@@ -0,0 +1,221 @@
+use itertools::{Itertools, MinMaxResult};
+use std::io::BufRead;
+
+use crate::{
+    generate::{self, Generated, Generator, Rng},
+    params, parse, Answers, Error, Param, Params, Solution,
+};
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {
+        read_numbers(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        Self::part1_with(input, &Params::default())
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        Self::part2_with(input, &Params::default())
+    }
+
+    const GENERATOR: Option<&'static Generator> = Some(&GENERATOR);
+
+    const PARAMS: &'static [Param] = &[PREAMBLE];
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Self::Answer1 {
+        part1(input, PREAMBLE.get(params))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Self::Answer2 {
+        part2(part1(input, PREAMBLE.get(params)), input)
+    }
+
+    fn stream<R: BufRead>(reader: R, params: &Params) -> Option<Result<Answers<Self>, Error>> {
+        Some(stream(reader, PREAMBLE.get(params)))
+    }
+}
+
+pub const PREAMBLE: Param = Param {
+    name: "preamble",
+    default: "25",
+    help: "Previous numbers a number has to be the sum of two of",
+    check: params::at_least::<usize, 1>,
+};
+
+pub fn part1(numbers: &[i64], preamble: usize) -> i64 {
+    *numbers
+        .iter()
+        .skip(preamble)
+        .enumerate()
+        .find(|(i, n)| !is_sum_of_previous(**n, &numbers[*i..*i + preamble]))
+        .expect("Solution not found!")
+        .1
+}
+
+pub fn part2(target: i64, numbers: &[i64]) -> i64 {
+    let result = numbers
+        .iter()
+        .enumerate()
+        .filter_map(|(i, _)| find_exact_sum_of_previous(target, &numbers[..i]))
+        .next()
+        .expect("Solution not found!");
+    result.0 + result.1
+}
+
+fn find_exact_sum_of_previous(n: i64, numbers: &[i64]) -> Option<(i64, i64)> {
+    let mut sum = 0;
+    let mut v: Vec<i64> = Vec::new();
+    for num in numbers.iter().rev() {
+        sum += num;
+        v.push(*num);
+
+        if sum > n {
+            return None;
+        }
+
+        if sum == n {
+            if let MinMaxResult::MinMax(min, max) = v.iter().minmax() {
+                return Some((*min, *max));
+            } else {
+                panic!("fiasco!");
+            }
+        }
+    }
+
+    None
+}
+
+fn is_sum_of_previous(n: i64, numbers: &[i64]) -> bool {
+    let sorted_numbers = numbers.iter().sorted().collect::<Vec<_>>();
+
+    numbers
+        .iter()
+        .any(|num| sorted_numbers.binary_search(&&(n - num)).is_ok())
+}
+
+/// Both answers keeping the numbers only up to the first invalid one, as the
+/// range of part 2 comes before it. Later numbers are just checked to parse.
+pub fn stream<R: BufRead>(reader: R, preamble: usize) -> Result<(i64, i64), Error> {
+    let mut numbers = Vec::new();
+    let mut invalid = None;
+    for number in parse::each_number(Day9::DAY, reader) {
+        let number = number?;
+        if invalid.is_none() {
+            let previous = &numbers[numbers.len().saturating_sub(preamble)..];
+            if previous.len() == preamble && !is_sum_of_previous(number, previous) {
+                invalid = Some(number);
+            }
+            numbers.push(number);
+        }
+    }
+    let invalid = invalid.expect("Solution not found!");
+    Ok((invalid, part2(invalid, &numbers)))
+}
+
+pub fn read_numbers<R: BufRead>(reader: R) -> Result<Vec<i64>, Error> {
+    parse::numbers(Day9::DAY, reader)
+}
+
+pub static GENERATOR: Generator = Generator {
+    unit: "numbers",
+    sizes: 50..=1000,
+    default_size: 1000,
+    build: generate,
+};
+
+/// Numbers which are sums of two of the previous 25 but one, which is the
+/// sum of a single contiguous range of earlier numbers.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let preamble: usize = PREAMBLE.get(&Params::default());
+    let mut numbers: Vec<i64> = (1..=100).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(preamble);
+
+    // The smallest number of the window plus one of the next few keeps the
+    // numbers growing about as slowly as a puzzle input's, sums of any two
+    // would overflow long before the end.
+    let next = |rng: &mut Rng, numbers: &[i64]| {
+        let mut window = numbers[numbers.len() - preamble..].to_vec();
+        window.sort_unstable();
+        window.dedup();
+        window[0] + rng.choose(&window[1..window.len().min(5)])
+    };
+    let invalid_at = rng.range(preamble as i64 + 10..=size as i64 - 1) as usize;
+    while numbers.len() < invalid_at {
+        let number = next(rng, &numbers);
+        numbers.push(number);
+    }
+
+    let (target, range) = loop {
+        let start = rng.index(invalid_at / 2);
+        let len = rng.range(3..=17) as usize;
+        let range = &numbers[start..invalid_at.min(start + len)];
+        let target: i64 = range.iter().sum();
+        if !is_sum_of_previous(target, &numbers[invalid_at - preamble..])
+            && contiguous_sums(&numbers, target) == 1
+        {
+            break (target, range.to_vec());
+        }
+    };
+    numbers.push(target);
+    while numbers.len() < size {
+        let number = next(rng, &numbers);
+        numbers.push(number);
+    }
+
+    let weakness = range.iter().min().unwrap() + range.iter().max().unwrap();
+    Generated::new(generate::lines(numbers))
+        .with_answer(1, target)
+        .with_answer(2, weakness)
+}
+
+/// Contiguous ranges of the positive `numbers` summing to `target`.
+fn contiguous_sums(numbers: &[i64], target: i64) -> usize {
+    let (mut start, mut sum, mut count) = (0, 0, 0);
+    for (end, number) in numbers.iter().enumerate() {
+        sum += number;
+        while sum > target {
+            sum -= numbers[start];
+            start += 1;
+        }
+        if sum == target && start <= end {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::BufReader};
+
+    #[test]
+    fn test_solution() {
+        let numbers =
+            read_numbers(BufReader::new(File::open("inputs/day9/1.txt").unwrap())).unwrap();
+        let part1_solution = part1(&numbers, 25);
+        assert_eq!(part1_solution, 177777905);
+        assert_eq!(part2(part1_solution, &numbers), 23463012);
+    }
+
+    #[test]
+    fn test_example() {
+        let numbers = read_numbers(BufReader::new(
+            File::open("inputs/day9/example.txt").unwrap(),
+        ))
+        .unwrap();
+        let part1_solution = part1(&numbers, 5);
+        assert_eq!(part1_solution, 127);
+        assert_eq!(part2(part1_solution, &numbers), 62);
+    }
+}
@@ -0,0 +1,134 @@
+use std::{fmt, io};
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "I/O error: {}", e),
+            Error::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+/// Malformed puzzle input.
+///
+/// Lines and columns are 1-based, columns count characters rather than bytes.
+/// Errors raised by `FromStr` implementations point at line 1 of the parsed
+/// string, the readers move them to the actual line with [`ParseError::on_line`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    /// The offending part of the input.
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    /// An error covering the whole `line`.
+    pub fn line(day: u8, line: &str, reason: impl Into<String>) -> Self {
+        ParseError {
+            day,
+            line: 1,
+            column: 1,
+            text: line.to_owned(),
+            reason: reason.into(),
+        }
+    }
+
+    /// An error covering `part`, which should be a slice of `line` for the
+    /// column to be found. Otherwise the error points at the first column.
+    pub fn within(day: u8, line: &str, part: &str, reason: impl Into<String>) -> Self {
+        let offset = (part.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+        let column = match line.get(..offset) {
+            Some(prefix) if offset + part.len() <= line.len() => prefix.chars().count() + 1,
+            _ => 1,
+        };
+        ParseError {
+            day,
+            line: 1,
+            column,
+            text: part.to_owned(),
+            reason: reason.into(),
+        }
+    }
+
+    /// An error at the end of the input, e.g. for a missing section.
+    pub fn end_of_input(day: u8, reason: impl Into<String>) -> Self {
+        ParseError {
+            day,
+            line: 0,
+            column: 0,
+            text: String::new(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "day {}, end of input: {}", self.day, self.reason)
+        } else {
+            write!(
+                f,
+                "day {}, line {}, column {}: {} (`{}`)",
+                self.day, self.line, self.column, self.reason, self.text
+            )
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_within() {
+        let line = "1-3 ą: abc";
+        let error = ParseError::within(2, line, &line[8..], "bad password").on_line(4);
+        assert_eq!((error.line, error.column), (4, 8));
+        assert_eq!(error.text, "abc");
+        assert_eq!(
+            error.to_string(),
+            "day 2, line 4, column 8: bad password (`abc`)"
+        );
+
+        let error = ParseError::within(2, line, "elsewhere", "bad password");
+        assert_eq!(error.column, 1);
+    }
+}
@@ -0,0 +1,185 @@
+//! Random puzzle inputs for stress tests and benchmarks, used by `aoc gen`.
+//!
+//! A day's [`Generator`], its [`crate::Solution::GENERATOR`], builds a valid
+//! input of a given size from a seed. Where the construction decides the
+//! answers, e.g. the pair of entries summing to 2020 on day 1, they come along
+//! with the input.
+
+use std::ops::RangeInclusive;
+
+/// SplitMix64, see https://prng.di.unimi.it/splitmix64.c. Small and good
+/// enough for inputs, and a seed gives the same input on every platform.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, e.g. `rng.range(1..=6)` for a die.
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (low, high) = range.into_inner();
+        assert!(low <= high, "empty range {}..={}", low, high);
+        let span = (i128::from(high) - i128::from(low) + 1) as u128;
+        (i128::from(low) + (u128::from(self.next_u64()) % span) as i128) as i64
+    }
+
+    /// An index into a slice of `len` items.
+    ///
+    /// # Panics
+    ///
+    /// If `len` is zero.
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "no index into an empty slice");
+        (self.next_u64() % len as u64) as usize
+    }
+
+    /// True with a probability of `percent` percent.
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.next_u64() % 100 < percent
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Fisher-Yates, see https://en.wikipedia.org/wiki/Fisher%E2%80%93Yates_shuffle.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+/// An input together with the answers known from building it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub answers: [Option<String>; 2],
+}
+
+impl Generated {
+    /// An input whose answers are only known by solving it.
+    pub fn new(input: String) -> Self {
+        Generated {
+            input,
+            answers: [None, None],
+        }
+    }
+
+    pub fn with_answer(mut self, part: u8, answer: impl ToString) -> Self {
+        self.answers[usize::from(part) - 1] = Some(answer.to_string());
+        self
+    }
+
+    /// The known answers in the format of a sidecar file, see
+    /// [`answers`](crate::answers).
+    pub fn sidecar(&self) -> String {
+        let mut sidecar = "# part, answer\n".to_owned();
+        for (part, answer) in (1..).zip(&self.answers) {
+            if let Some(answer) = answer {
+                sidecar += &format!("{} {}\n", part, answer);
+            }
+        }
+        sidecar
+    }
+}
+
+/// Builds inputs of one day.
+pub struct Generator {
+    /// What the size counts, e.g. `entries`.
+    pub unit: &'static str,
+    pub sizes: RangeInclusive<usize>,
+    pub default_size: usize,
+    pub build: fn(&mut Rng, usize) -> Generated,
+}
+
+impl Generator {
+    pub fn generate(&self, seed: u64, size: Option<usize>) -> Result<Generated, String> {
+        let size = size.unwrap_or(self.default_size);
+        if !self.sizes.contains(&size) {
+            return Err(format!(
+                "size must be {} to {} {}",
+                self.sizes.start(),
+                self.sizes.end(),
+                self.unit
+            ));
+        }
+        Ok((self.build)(&mut Rng::new(seed), size))
+    }
+}
+
+/// Random lowercase word of `len` letters.
+pub fn word(rng: &mut Rng, len: usize) -> String {
+    (0..len)
+        .map(|_| (b'a' + rng.index(26) as u8) as char)
+        .collect()
+}
+
+/// One line per item, each followed by a newline.
+pub fn lines<T: ToString>(items: impl IntoIterator<Item = T>) -> String {
+    items
+        .into_iter()
+        .map(|item| item.to_string() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_generate() {
+        let mut rng = Rng::new(7);
+        let first: Vec<_> = (0..3).map(|_| rng.next_u64()).collect();
+        let mut rng = Rng::new(7);
+        assert_eq!((0..3).map(|_| rng.next_u64()).collect::<Vec<_>>(), first);
+        assert!((0..100).all(|_| (-2..=2).contains(&rng.range(-2..=2))));
+
+        for solution in registry::all() {
+            let day = solution.day();
+            let generator = match solution.generator() {
+                Some(generator) => generator,
+                None => continue,
+            };
+            let small = *generator.sizes.start();
+            assert!(generator
+                .generate(1, Some(generator.sizes.end() + 1))
+                .is_err());
+            for (seed, size) in [(1, small), (2, small.max(generator.default_size / 4))] {
+                let generated = generator.generate(seed, Some(size)).unwrap();
+                assert_eq!(generator.generate(seed, Some(size)), Ok(generated.clone()));
+                let input = solution
+                    .parse(&mut generated.input.as_bytes())
+                    .unwrap_or_else(|e| panic!("day {}, seed {}: {}", day, seed, e));
+                for (part, answer) in (1..).zip(&generated.answers) {
+                    if let Some(answer) = answer {
+                        assert_eq!(
+                            &solution.solve(input.as_ref(), part),
+                            answer,
+                            "day {}, part {}, seed {}",
+                            day,
+                            part,
+                            seed
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
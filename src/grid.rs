@@ -0,0 +1,345 @@
+//! A rectangular grid of cells, shared by the puzzles played out on a map.
+//!
+//! Positions are `(x, y)` pairs with `x` growing to the right and `y` growing
+//! downwards, the way the input is read.
+
+use std::{
+    fmt::{self, Display},
+    io::BufRead,
+    iter,
+    ops::{Index, IndexMut},
+};
+
+use crate::{parse, Error, ParseError};
+
+pub type Position = (usize, usize);
+
+/// Step from one position to another, e.g. `(1, -1)` for up and to the right.
+pub type Direction = (isize, isize);
+
+/// Directions of the four orthogonal neighbours, clockwise from up.
+pub const ORTHOGONAL: [Direction; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Directions of all eight neighbours, clockwise from up.
+pub const ALL: [Direction; 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid without any rows, to be filled with [`Grid::push_row`].
+    pub fn empty() -> Self {
+        Grid {
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Position) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut cell)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Reads one row per line, mapping every character with `cell`. A
+    /// character `cell` doesn't accept is reported with `expected` as the
+    /// reason, as are rows of different lengths and an empty input.
+    pub fn parse<R, F>(day: u8, reader: R, cell: F, expected: &str) -> Result<Self, Error>
+    where
+        R: BufRead,
+        F: Fn(char) -> Option<T>,
+    {
+        let mut grid = Grid::empty();
+        for line in parse::lines(reader) {
+            let (num, line) = line?;
+            let row = Grid::parse_row(day, &line, &cell, expected).map_err(|e| e.on_line(num))?;
+            grid.push_row(row)
+                .map_err(|e| ParseError::line(day, &line, e).on_line(num))?;
+        }
+
+        if grid.is_empty() {
+            return Err(ParseError::end_of_input(day, "empty grid").into());
+        }
+        Ok(grid)
+    }
+
+    /// Maps every character of `line` with `cell`, see [`Grid::parse`].
+    pub fn parse_row<F>(day: u8, line: &str, cell: F, expected: &str) -> Result<Vec<T>, ParseError>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        line.char_indices()
+            .map(|(i, ch)| {
+                cell(ch).ok_or_else(|| {
+                    ParseError::within(day, line, &line[i..i + ch.len_utf8()], expected)
+                })
+            })
+            .collect()
+    }
+
+    /// Appends a row at the bottom. The first row sets the width, the rest
+    /// have to match it.
+    pub fn push_row(&mut self, row: Vec<T>) -> Result<(), String> {
+        let width = if self.is_empty() {
+            row.len()
+        } else {
+            self.width
+        };
+        if row.is_empty() || row.len() != width {
+            return Err(format!("expected {} columns", width.max(1)));
+        }
+        self.width = width;
+        self.height += 1;
+        self.cells.extend(row);
+        Ok(())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, (x, y): Position) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): Position) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `(x, y)` of a map repeating the grid in every direction.
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    /// The position one step from `(x, y)` in `direction`, if it is inside.
+    pub fn step(&self, (x, y): Position, (dx, dy): Direction) -> Option<Position> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    /// Positions of the orthogonal neighbours inside the grid.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ORTHOGONAL
+            .iter()
+            .filter_map(move |&direction| self.step(position, direction))
+    }
+
+    /// Positions of all neighbours inside the grid, diagonal ones included.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ALL.iter()
+            .filter_map(move |&direction| self.step(position, direction))
+    }
+
+    /// Positions seen from `position` looking in `direction`, nearest first,
+    /// up to the edge of the grid. `position` itself is not included.
+    pub fn ray(
+        &self,
+        position: Position,
+        direction: Direction,
+    ) -> impl Iterator<Item = Position> + '_ {
+        iter::successors(self.step(position, direction), move |&position| {
+            self.step(position, direction)
+        })
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn map<U>(&self, cell: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(cell).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// The grid turned a quarter clockwise.
+    pub fn rotate(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// The grid mirrored left to right.
+    pub fn flip(&self) -> Self {
+        Grid::from_fn(self.width, self.height, |(x, y)| {
+            self[(self.width - 1 - x, y)].clone()
+        })
+    }
+
+    /// The grid mirrored along the diagonal from the top left corner.
+    pub fn transpose(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    /// The `width` × `height` part of the grid with its top left corner at `(x, y)`.
+    pub fn crop(&self, (x, y): Position, width: usize, height: usize) -> Self {
+        Grid::from_fn(width, height, |(dx, dy)| self[(x + dx, y + dy)].clone())
+    }
+
+    /// The eight ways of laying the grid down: four rotations, then the same
+    /// four of the flipped grid.
+    pub fn orientations(&self) -> impl Iterator<Item = Self> {
+        let rotations =
+            |grid: Self| iter::successors(Some(grid), |grid| Some(grid.rotate())).take(4);
+        rotations(self.clone()).chain(rotations(self.flip()))
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} outside of a {}x{} grid",
+                position, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} outside of a {}x{} grid",
+                position, width, height
+            )
+        })
+    }
+}
+
+/// One line per row, without a newline after the last one.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in self.row(y) {
+                cell.fmt(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let parse = |text: &str| {
+            Grid::parse(
+                0,
+                text.as_bytes(),
+                |ch| Some(ch).filter(|&ch| ch != '?'),
+                "no `?`",
+            )
+        };
+        let grid = parse("ab.\n.#c\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.to_string(), "ab.\n.#c");
+        assert_eq!(grid.get((2, 1)), Some(&'c'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get_wrapping(-1, 3), &'c');
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+        assert_eq!(
+            grid.ray((0, 0), (1, 0)).collect::<Vec<_>>(),
+            [(1, 0), (2, 0)]
+        );
+        assert_eq!(grid.column(1).collect::<String>(), "b#");
+
+        assert_eq!(grid.rotate().to_string(), ".a\n#b\nc.");
+        assert_eq!(grid.flip().to_string(), ".ba\nc#.");
+        assert_eq!(grid.transpose(), grid.rotate().flip());
+        assert_eq!(grid.crop((1, 0), 2, 2).to_string(), "b.\n#c");
+        let orientations: Vec<_> = grid.orientations().collect();
+        assert_eq!(orientations.len(), 8);
+        assert!(orientations.contains(&grid.transpose()));
+
+        let error = |text| parse(text).unwrap_err().to_string();
+        assert_eq!(error("ab\nc?\n"), "day 0, line 2, column 2: no `?` (`?`)");
+        assert_eq!(
+            error("ab\nc\n"),
+            "day 0, line 2, column 1: expected 2 columns (`c`)"
+        );
+        assert!(error("").contains("empty grid"));
+    }
+}
@@ -0,0 +1,116 @@
+//! The local store of puzzle inputs, `inputs/dayN/<name>.txt`.
+//!
+//! `1.txt` is the default input of a day, `example*.txt` are the examples from
+//! the puzzle statement, with their answers in sidecar files.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{registry::DynSolution, Error};
+
+pub const DIR: &str = "inputs";
+
+pub fn day_dir(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{}", day))
+}
+
+/// Every input of the day, sorted by name.
+pub fn list(dir: &Path, day: u8) -> io::Result<Vec<PathBuf>> {
+    let mut inputs = Vec::new();
+    for entry in fs::read_dir(day_dir(dir, day))? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "txt") {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+/// Drops a byte order mark, turns `\r\n` and `\r` into `\n` and ends the
+/// input with exactly one newline, so that copies of the same input made on
+/// different systems hash the same.
+pub fn normalize(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut normalized = input.replace("\r\n", "\n").replace('\r', "\n");
+    normalized.truncate(normalized.trim_end_matches('\n').len());
+    normalized.push('\n');
+    normalized
+}
+
+/// Normalizes `data` and stores it as `<name>.txt` among the inputs of the
+/// day, after checking that the day can parse it. Existing inputs are only
+/// replaced with `force`.
+pub fn add(
+    dir: &Path,
+    solution: &dyn DynSolution,
+    name: &str,
+    data: &[u8],
+    force: bool,
+) -> Result<PathBuf, Error> {
+    let invalid = |reason: String| Error::from(io::Error::new(io::ErrorKind::InvalidInput, reason));
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err(invalid(format!(
+            "invalid name `{}`, use letters, digits, `-` and `_`",
+            name
+        )));
+    }
+    if name.starts_with("example") {
+        return Err(invalid(
+            "examples need a sidecar with their answers, add them by hand".to_owned(),
+        ));
+    }
+
+    let text =
+        std::str::from_utf8(data).map_err(|e| invalid(format!("input is not UTF-8: {}", e)))?;
+    let normalized = normalize(text);
+    solution.parse(&mut normalized.as_bytes())?;
+
+    let day_dir = day_dir(dir, solution.day());
+    fs::create_dir_all(&day_dir)?;
+    let path = day_dir.join(format!("{}.txt", name));
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .create_new(!force)
+        .open(&path)?
+        .write_all(normalized.as_bytes())?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_add() {
+        assert_eq!(normalize("\u{feff}1\r\n2\r3\n\n\n"), "1\n2\n3\n");
+        assert_eq!(normalize("1"), "1\n");
+
+        let dir = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        let day1 = registry::find(1).unwrap();
+        let path = add(&dir, day1, "alice", b"1721\r\n979\r\n", false).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1721\n979\n");
+        assert_eq!(list(&dir, 1).unwrap(), vec![path.clone()]);
+
+        assert!(add(&dir, day1, "alice", b"1721\n", false).is_err());
+        add(&dir, day1, "alice", b"1721\n", true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1721\n");
+
+        let error = add(&dir, day1, "bob", b"1721\nabc\n", false).unwrap_err();
+        assert!(matches!(error, Error::Parse(_)));
+        assert!(add(&dir, day1, "../bob", b"1721\n", false).is_err());
+        assert!(!day_dir(&dir, 1).join("bob.txt").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
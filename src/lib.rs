@@ -0,0 +1,96 @@
+use std::{
+    fmt::{self, Display},
+    io::{self, BufRead},
+    process,
+};
+
+pub mod answers;
+pub mod automaton;
+pub mod bench;
+pub mod days;
+pub mod error;
+pub mod generate;
+pub mod grid;
+pub mod inputs;
+pub mod math;
+pub mod params;
+pub mod parse;
+pub mod registry;
+pub mod render;
+pub mod scaffold;
+
+pub use error::{Error, ParseError};
+pub use generate::Generator;
+pub use params::{Param, Params};
+
+/// The answers to both parts of a [`Solution`].
+pub type Answers<S> = (<S as Solution>::Answer1, <S as Solution>::Answer2);
+
+/// Answer to a part a day doesn't have, shown as `-` like in the answer
+/// files. See [`Solution::PARTS`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NoAnswer;
+
+impl Display for NoAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "-")
+    }
+}
+
+/// A single day of the Advent of Code calendar.
+///
+/// The input is parsed once and then shared by both parts.
+pub trait Solution {
+    /// Day of the calendar, `1..=25`.
+    const DAY: u8;
+    /// Number of parts the puzzle has. Day 25 only has one.
+    const PARTS: u8 = 2;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    /// Builds random inputs of the day, `None` for days without one.
+    const GENERATOR: Option<&'static Generator> = None;
+
+    /// Values the puzzle fixes which its variants change, e.g. the number of
+    /// moves on day 23.
+    const PARAMS: &'static [Param] = &[];
+
+    /// [`Solution::part1`] with `params` overriding [`Solution::PARAMS`].
+    fn part1_with(input: &Self::Input, _params: &Params) -> Self::Answer1 {
+        Self::part1(input)
+    }
+
+    /// [`Solution::part2`] with `params` overriding [`Solution::PARAMS`].
+    fn part2_with(input: &Self::Input, _params: &Params) -> Self::Answer2 {
+        Self::part2(input)
+    }
+
+    /// Solves both parts in a single pass over the input, keeping only what
+    /// the answers depend on instead of the whole input. `None` for days
+    /// that need their input in full.
+    fn stream<R: BufRead>(_reader: R, _params: &Params) -> Option<Result<Answers<Self>, Error>> {
+        None
+    }
+}
+
+/// Reads the puzzle input from stdin and prints the answers to all parts. An
+/// invalid input is reported on stderr, exiting with status 1.
+pub fn solve_stdin<S: Solution>() {
+    let input = match S::parse(io::stdin().lock()) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Day {}: invalid input: {}", S::DAY, e);
+            process::exit(1);
+        }
+    };
+    println!("Day {}, part 1: {}", S::DAY, S::part1(&input));
+    if S::PARTS > 1 {
+        println!("Day {}, part 2: {}", S::DAY, S::part2(&input));
+    }
+}
@@ -0,0 +1,234 @@
+//! Modular arithmetic for the puzzles built on number theory.
+//!
+//! Everything works on the full `u64` range, products are taken in `u128` so
+//! nothing overflows.
+
+use std::{collections::HashMap, fmt};
+
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Calculates the extended GCD
+/// https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
+/// Returns tuple (GCD, x, y)
+/// Satisfying the ax + by = gcd(a,b)
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if a == 0 {
+        (b, 0, 1)
+    } else {
+        let (gcd, x1, y1) = extended_gcd(b % a, a);
+        (gcd, y1 - (b / a) * x1, x1)
+    }
+}
+
+/// `a * b mod m`.
+///
+/// # Panics
+///
+/// If `m` is zero.
+pub fn mulmod(a: u64, b: u64, m: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(m)) as u64
+}
+
+/// `base^exp mod m`, by repeated squaring.
+///
+/// # Panics
+///
+/// If `m` is zero.
+pub fn powmod(base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut base = base % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mulmod(result, base, m);
+        }
+        base = mulmod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Calculates the modular multiplicative inverse, if `a` and `m` are coprime
+/// https://en.wikipedia.org/wiki/Modular_multiplicative_inverse
+pub fn mod_inv(a: u64, m: u64) -> Option<u64> {
+    let (g, x, _) = extended_gcd(i128::from(a % m), i128::from(m));
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(i128::from(m)) as u64)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrtError {
+    /// A modulus is zero.
+    ZeroModulus,
+    /// The congruences contradict each other, e.g. `x ≡ 0 (mod 2)` and
+    /// `x ≡ 1 (mod 4)`.
+    Unsolvable,
+    /// The least common multiple of the moduli doesn't fit into a `u64`.
+    Overflow,
+}
+
+impl fmt::Display for CrtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrtError::ZeroModulus => write!(f, "modulus is zero"),
+            CrtError::Unsolvable => write!(f, "congruences contradict each other"),
+            CrtError::Overflow => write!(f, "combined modulus overflows"),
+        }
+    }
+}
+
+impl std::error::Error for CrtError {}
+
+/// Solves the system of `x ≡ residue (mod modulus)` congruences, see
+/// https://en.wikipedia.org/wiki/Chinese_remainder_theorem. The moduli don't
+/// have to be coprime.
+///
+/// Returns the smallest non-negative solution together with the least common
+/// multiple of the moduli, every solution differs from it by a multiple of it.
+pub fn crt(congruences: &[(i64, u64)]) -> Result<(u64, u64), CrtError> {
+    let (mut x, mut lcm) = (0, 1);
+    for &(residue, modulus) in congruences {
+        if modulus == 0 {
+            return Err(CrtError::ZeroModulus);
+        }
+        let residue = i128::from(residue).rem_euclid(i128::from(modulus)) as u64;
+
+        // x + lcm * k ≡ residue (mod modulus) has a solution for k only if
+        // gcd(lcm, modulus) divides the difference.
+        let g = gcd(lcm, modulus);
+        let difference = (i128::from(residue) - i128::from(x)).rem_euclid(i128::from(modulus));
+        if difference % i128::from(g) != 0 {
+            return Err(CrtError::Unsolvable);
+        }
+        let step = modulus / g;
+        let inverse = mod_inv(lcm / g, step).expect("divided by the common factors");
+        let k = mulmod((difference / i128::from(g)) as u64 % step, inverse, step);
+
+        let next = u128::from(lcm / g) * u128::from(modulus);
+        if next > u128::from(u64::MAX) {
+            return Err(CrtError::Overflow);
+        }
+        x = ((u128::from(x) + u128::from(lcm) * u128::from(k)) % next) as u64;
+        lcm = next as u64;
+    }
+    Ok((x, lcm))
+}
+
+/// The smallest `x` with `base^x ≡ target (mod m)`, by baby-step giant-step
+/// https://en.wikipedia.org/wiki/Baby-step_giant-step. Takes time and memory
+/// in the order of the square root of `m`.
+///
+/// # Panics
+///
+/// If `m` is zero.
+pub fn discrete_log(base: u64, target: u64, m: u64) -> Option<u64> {
+    let (mut base, mut target, mut m) = (base % m, target % m, m);
+    // Solves factor * base^x ≡ target, dividing out the factors base and m
+    // have in common first, as the giant steps need base to be invertible.
+    let (mut factor, mut offset) = (1 % m, 0);
+    loop {
+        if factor == target {
+            return Some(offset);
+        }
+        let g = gcd(base, m);
+        if g == 1 {
+            break;
+        }
+        if target % g != 0 {
+            return None;
+        }
+        target /= g;
+        m /= g;
+        factor = mulmod(factor, base / g, m);
+        base %= m;
+        offset += 1;
+    }
+
+    let n = (m as f64).sqrt().ceil() as u64 + 1;
+    // For x = i * n - j, the largest j gives the smallest x.
+    let mut baby_steps = HashMap::new();
+    let mut value = target;
+    for j in 0..n {
+        baby_steps.insert(value, j);
+        value = mulmod(value, base, m);
+    }
+
+    let giant_step = powmod(base, n, m);
+    let mut value = factor;
+    for i in 1..=n {
+        value = mulmod(value, giant_step, m);
+        if let Some(j) = baby_steps.get(&value) {
+            return Some(i * n - j + offset);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_math() {
+        assert_eq!(mulmod(u64::MAX, u64::MAX, 1_000_000_007), 114_944_269);
+        assert_eq!(powmod(7, 8, 20_201_227), 5_764_801);
+        assert_eq!(powmod(7, 20_201_226, 20_201_227), 1);
+        assert_eq!(mod_inv(3, 7), Some(5));
+        assert_eq!(mod_inv(2, 4), None);
+        assert_eq!(crt(&[(0, 3), (3, 4), (4, 5)]), Ok((39, 60)));
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Ok((10, 12)));
+        assert_eq!(crt(&[(0, 2), (1, 4)]), Err(CrtError::Unsolvable));
+        assert_eq!(crt(&[(0, u64::MAX), (0, 2)]), Err(CrtError::Overflow));
+        assert_eq!(crt(&[(1, 0)]), Err(CrtError::ZeroModulus));
+        assert_eq!(discrete_log(7, 5_764_801, 20_201_227), Some(8));
+        assert_eq!(discrete_log(2, 0, 16), Some(4));
+        assert_eq!(discrete_log(2, 3, 16), None);
+    }
+
+    proptest! {
+        #[test]
+        fn mulmod_matches_u128(a: u64, b: u64, m in 1..=u64::MAX) {
+            prop_assert_eq!(u128::from(mulmod(a, b, m)), u128::from(a) * u128::from(b) % u128::from(m));
+        }
+
+        #[test]
+        fn powmod_adds_exponents(base: u64, e1 in 0..1u64 << 32, e2 in 0..1u64 << 32, m in 1..=u64::MAX) {
+            let product = mulmod(powmod(base, e1, m), powmod(base, e2, m), m);
+            prop_assert_eq!(powmod(base, e1 + e2, m), product);
+        }
+
+        #[test]
+        fn crt_agrees_with_search(congruences in prop::collection::vec((-50..50i64, 1..30u64), 1..4)) {
+            let satisfies = |x: u64| {
+                congruences
+                    .iter()
+                    .all(|&(r, m)| i128::from(x) % i128::from(m) == i128::from(r).rem_euclid(i128::from(m)))
+            };
+            let lcm = congruences.iter().fold(1, |lcm, &(_, m)| lcm / gcd(lcm, m) * m);
+            match crt(&congruences) {
+                Ok((x, modulus)) => {
+                    prop_assert_eq!(modulus, lcm);
+                    prop_assert_eq!((0..lcm).find(|&x| satisfies(x)), Some(x));
+                }
+                Err(e) => {
+                    prop_assert_eq!(e, CrtError::Unsolvable);
+                    prop_assert!(!(0..lcm).any(satisfies));
+                }
+            }
+        }
+
+        #[test]
+        fn discrete_log_agrees_with_search(base in 0..200u64, target in 0..200u64, m in 1..200u64) {
+            let expected = (0..2 * m).find(|&x| powmod(base, x, m) == target % m);
+            prop_assert_eq!(discrete_log(base, target, m), expected);
+        }
+    }
+}
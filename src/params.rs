@@ -0,0 +1,181 @@
+//! Values a puzzle fixes which its variants change, e.g. the 2020 the
+//! entries of day 1 add up to.
+
+use std::{any, collections::HashMap, convert::TryInto, fmt::Display, str::FromStr};
+
+/// A parameter of a day, see [`crate::Solution::PARAMS`].
+pub struct Param {
+    pub name: &'static str,
+    /// Value the puzzle uses.
+    pub default: &'static str,
+    pub help: &'static str,
+    /// Tells what is expected if a value doesn't fit.
+    pub check: fn(&str) -> Result<(), String>,
+}
+
+impl Param {
+    /// The value of this parameter, its default unless overridden in `params`.
+    ///
+    /// Panics if the value doesn't pass [`Param::check`].
+    pub fn get<T: FromStr>(&self, params: &Params) -> T {
+        let value = params
+            .values
+            .get(self.name)
+            .map_or(self.default, String::as_str);
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid {} `{}`", self.name, value))
+    }
+}
+
+/// Overridden parameters of a day, the rest keeping their defaults.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: HashMap<&'static str, String>,
+}
+
+impl Params {
+    /// Checks `name=value` overrides against the parameters of a day.
+    pub fn new(params: &'static [Param], overrides: &[String]) -> Result<Self, String> {
+        let mut values = HashMap::new();
+        for assignment in overrides {
+            let (name, value) = assignment
+                .split_once('=')
+                .ok_or_else(|| format!("expected `name=value`, got `{}`", assignment))?;
+            let param =
+                params
+                    .iter()
+                    .find(|param| param.name == name)
+                    .ok_or_else(|| match params {
+                        [] => format!("unknown parameter `{}`, the day has none", name),
+                        _ => format!(
+                            "unknown parameter `{}`, expected one of {}",
+                            name,
+                            params
+                                .iter()
+                                .map(|param| param.name)
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    })?;
+            (param.check)(value).map_err(|e| format!("invalid {} `{}`: {}", name, value, e))?;
+            values.insert(param.name, value.to_owned());
+        }
+        Ok(Params { values })
+    }
+}
+
+/// Accepts any value of type `T`.
+pub fn number<T: FromStr>(value: &str) -> Result<(), String>
+where
+    T::Err: Display,
+{
+    value.parse::<T>().map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// Accepts values of type `T` from `MIN` on.
+pub fn at_least<T, const MIN: u64>(value: &str) -> Result<(), String>
+where
+    T: FromStr + TryInto<u64>,
+{
+    match unsigned::<T>(value) {
+        Some(number) if number >= MIN => Ok(()),
+        _ => Err(format!(
+            "expected a {} of at least {}",
+            any::type_name::<T>(),
+            MIN
+        )),
+    }
+}
+
+/// Accepts values of type `T` from `MIN` to `MAX`, for values that decide how
+/// much memory a solution takes.
+pub fn between<T, const MIN: u64, const MAX: u64>(value: &str) -> Result<(), String>
+where
+    T: FromStr + TryInto<u64>,
+{
+    match unsigned::<T>(value) {
+        Some(number) if (MIN..=MAX).contains(&number) => Ok(()),
+        _ => Err(format!(
+            "expected a {} from {} to {}",
+            any::type_name::<T>(),
+            MIN,
+            MAX
+        )),
+    }
+}
+
+/// `value` as a `T`, if it is one and fits in a `u64`.
+fn unsigned<T>(value: &str) -> Option<u64>
+where
+    T: FromStr + TryInto<u64>,
+{
+    value
+        .parse::<T>()
+        .ok()
+        .and_then(|number| number.try_into().ok())
+}
+
+/// Accepts anything but an empty value.
+pub fn text(value: &str) -> Result<(), String> {
+    match value {
+        "" => Err("expected a non-empty value".to_owned()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static PARAMS: &[Param] = &[
+        Param {
+            name: "target",
+            default: "2020",
+            help: "Sum of the entries",
+            check: number::<i32>,
+        },
+        Param {
+            name: "turns",
+            default: "10",
+            help: "Turns to play",
+            check: at_least::<usize, 1>,
+        },
+        Param {
+            name: "cups",
+            default: "9",
+            help: "Cups in the circle",
+            check: between::<u32, 2, 100>,
+        },
+    ];
+
+    #[test]
+    fn test_params() {
+        let params = Params::new(PARAMS, &["target=-5".to_owned()]).unwrap();
+        assert_eq!(PARAMS[0].get::<i32>(&params), -5);
+        assert_eq!(PARAMS[1].get::<usize>(&params), 10);
+
+        let error = |overrides: &[&str]| {
+            let overrides: Vec<_> = overrides.iter().map(|&o| o.to_owned()).collect();
+            Params::new(PARAMS, &overrides).unwrap_err()
+        };
+        assert_eq!(error(&["target"]), "expected `name=value`, got `target`");
+        assert_eq!(
+            error(&["size=9"]),
+            "unknown parameter `size`, expected one of target, turns, cups"
+        );
+        assert_eq!(
+            error(&["turns=0"]),
+            "invalid turns `0`: expected a usize of at least 1"
+        );
+        assert_eq!(
+            error(&["cups=101"]),
+            "invalid cups `101`: expected a u32 from 2 to 100"
+        );
+        assert!(Params::new(PARAMS, &["cups=100".to_owned()]).is_ok());
+        assert_eq!(
+            Params::new(&[], &["turns=1".to_owned()]).unwrap_err(),
+            "unknown parameter `turns`, the day has none"
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! Helpers shared by the input readers.
+
+use std::{collections::HashSet, fmt::Display, hash::Hash, io::BufRead, str::FromStr};
+
+use crate::error::{Error, ParseError};
+
+/// Lines of the input together with their 1-based numbers. I/O errors are
+/// passed on instead of silently ending the input.
+pub fn lines<R: BufRead>(reader: R) -> impl Iterator<Item = Result<(usize, String), Error>> {
+    reader
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.map(|line| (i + 1, line)).map_err(Error::from))
+}
+
+/// Parses `part`, a slice of `line`, pointing at it on failure.
+pub fn number<T>(day: u8, line: &str, part: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    part.parse()
+        .map_err(|e| ParseError::within(day, line, part, format!("invalid number: {}", e)))
+}
+
+/// One record per line, parsed by `parse` as the lines are read. Blank
+/// lines are skipped, errors point at the line they come from.
+pub fn records<R, T, F>(reader: R, mut parse: F) -> impl Iterator<Item = Result<T, Error>>
+where
+    R: BufRead,
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    lines(reader).filter_map(move |line| match line {
+        Ok((_, line)) if line.is_empty() => None,
+        Ok((num, line)) => Some(parse(&line).map_err(|e| e.on_line(num).into())),
+        Err(e) => Some(Err(e)),
+    })
+}
+
+/// One number per line as the lines are read, blank lines are skipped.
+pub fn each_number<R, T>(day: u8, reader: R) -> impl Iterator<Item = Result<T, Error>>
+where
+    R: BufRead,
+    T: FromStr,
+    T::Err: Display,
+{
+    records(reader, move |line| number(day, line, line))
+}
+
+/// A node leading back to itself through `edges`, looking from each of
+/// `nodes` in turn. Searches depth-first with a stack of its own, so a long
+/// chain of rules can't overflow the real one.
+pub fn find_cycle<N, E, I>(nodes: impl IntoIterator<Item = N>, edges: E) -> Option<N>
+where
+    N: Clone + Eq + Hash,
+    E: Fn(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut done = HashSet::new();
+    for start in nodes {
+        if done.contains(&start) {
+            continue;
+        }
+        let mut path = HashSet::new();
+        path.insert(start.clone());
+        let mut stack = vec![(start.clone(), edges(&start).into_iter())];
+        while let Some((node, next)) = stack.last_mut() {
+            match next.next() {
+                Some(next) if path.contains(&next) => return Some(next),
+                Some(next) if done.contains(&next) => {}
+                Some(next) => {
+                    path.insert(next.clone());
+                    let edges = edges(&next).into_iter();
+                    stack.push((next, edges));
+                }
+                None => {
+                    path.remove(node);
+                    done.insert(node.clone());
+                    stack.pop();
+                }
+            }
+        }
+    }
+    None
+}
+
+/// One number per line, blank lines are skipped.
+pub fn numbers<R, T>(day: u8, reader: R) -> Result<Vec<T>, Error>
+where
+    R: BufRead,
+    T: FromStr,
+    T::Err: Display,
+{
+    each_number(day, reader).collect()
+}
@@ -0,0 +1,226 @@
+use std::{
+    any::{self, Any},
+    io::BufRead,
+};
+
+use crate::{days::DAYS, Error, Generator, Param, Params, Solution};
+
+/// Object-safe view of a [`Solution`], so that days with different input and
+/// answer types can be stored and driven side by side.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    fn parts(&self) -> u8;
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Box<dyn Any>, Error>;
+
+    /// Solves the given part for an input returned by [`DynSolution::parse`].
+    ///
+    /// Panics if the input comes from another day or the part does not exist.
+    fn solve(&self, input: &dyn Any, part: u8) -> String {
+        self.solve_with(input, part, &Params::default())
+    }
+
+    /// [`DynSolution::solve`] with `params` overriding the puzzle's values.
+    fn solve_with(&self, input: &dyn Any, part: u8, params: &Params) -> String;
+
+    /// Values the puzzle fixes which its variants change, see
+    /// [`Solution::PARAMS`].
+    fn params(&self) -> &'static [Param];
+
+    /// Random inputs of the day, see [`Solution::GENERATOR`].
+    fn generator(&self) -> Option<&'static Generator>;
+
+    /// Name of the type the given part's answer has before formatting.
+    fn answer_type(&self, part: u8) -> &'static str;
+
+    /// Both answers from a single pass over the input, see
+    /// [`Solution::stream`].
+    fn stream(
+        &self,
+        reader: &mut dyn BufRead,
+        params: &Params,
+    ) -> Option<Result<[String; 2], Error>>;
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync,
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parts(&self) -> u8 {
+        S::PARTS
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Box<dyn Any>, Error> {
+        Ok(Box::new(S::parse(reader)?))
+    }
+
+    fn solve_with(&self, input: &dyn Any, part: u8, params: &Params) -> String {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .unwrap_or_else(|| panic!("input does not belong to day {}", S::DAY));
+        match part {
+            1 => S::part1_with(input, params).to_string(),
+            2 if S::PARTS > 1 => S::part2_with(input, params).to_string(),
+            _ => panic!("day {} has no part {}", S::DAY, part),
+        }
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+
+    fn generator(&self) -> Option<&'static Generator> {
+        S::GENERATOR
+    }
+
+    fn answer_type(&self, part: u8) -> &'static str {
+        match part {
+            1 => any::type_name::<S::Answer1>(),
+            _ => any::type_name::<S::Answer2>(),
+        }
+    }
+
+    fn stream(
+        &self,
+        reader: &mut dyn BufRead,
+        params: &Params,
+    ) -> Option<Result<[String; 2], Error>> {
+        let answers = S::stream(reader, params)?;
+        Some(answers.map(|(answer1, answer2)| [answer1.to_string(), answer2.to_string()]))
+    }
+}
+
+pub fn all() -> &'static [&'static dyn DynSolution] {
+    DAYS
+}
+
+pub fn find(day: u8) -> Option<&'static dyn DynSolution> {
+    DAYS.iter().copied().find(|solution| solution.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::{
+        fs,
+        io::{self, BufReader, Read},
+        panic::{self, AssertUnwindSafe},
+    };
+
+    #[test]
+    fn test_registry() {
+        let days: Vec<_> = all().iter().map(|solution| solution.day()).collect();
+        assert_eq!(days, (1..=25).collect::<Vec<_>>());
+        assert!(find(26).is_none());
+    }
+
+    #[test]
+    fn test_solve() {
+        let day = find(1).unwrap();
+        let input = day
+            .parse(&mut "1721\n979\n366\n299\n675\n1456".as_bytes())
+            .unwrap();
+        assert_eq!(day.solve(input.as_ref(), 1), "514579");
+        assert_eq!(day.solve(input.as_ref(), 2), "241861950");
+        assert_eq!(
+            day.answer_type(1),
+            "advent_of_rust_2020::days::day1::Product"
+        );
+    }
+
+    #[test]
+    fn test_stream() {
+        let streaming = [1, 2, 5, 9, 12, 18, 24];
+        for solution in all() {
+            let day = solution.day();
+            let data = fs::read(format!("inputs/day{}/1.txt", day)).unwrap();
+            let answers = solution.stream(&mut data.as_slice(), &Params::default());
+            assert_eq!(answers.is_some(), streaming.contains(&day), "day {}", day);
+            if let Some(answers) = answers {
+                let input = solution.parse(&mut data.as_slice()).unwrap();
+                let expected = [
+                    solution.solve(input.as_ref(), 1),
+                    solution.solve(input.as_ref(), 2),
+                ];
+                assert_eq!(answers.unwrap(), expected, "day {}", day);
+            }
+        }
+
+        // Reading stops at the first I/O error instead of taking what came before as the input.
+        let broken = "1721\n979\n".as_bytes().chain(Broken);
+        let error = find(1)
+            .unwrap()
+            .stream(&mut BufReader::new(broken), &Params::default())
+            .unwrap();
+        assert!(matches!(error, Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn test_params() {
+        let day = find(9).unwrap();
+        let data = fs::read("inputs/day9/example.txt").unwrap();
+        let params = Params::new(day.params(), &["preamble=5".to_owned()]).unwrap();
+        let input = day.parse(&mut data.as_slice()).unwrap();
+        assert_eq!(day.solve_with(input.as_ref(), 1, &params), "127");
+        assert_eq!(day.solve_with(input.as_ref(), 2, &params), "62");
+        let answers = day.stream(&mut data.as_slice(), &params).unwrap().unwrap();
+        assert_eq!(answers, ["127", "62"]);
+
+        for solution in all() {
+            let defaults: Vec<_> = solution
+                .params()
+                .iter()
+                .map(|param| format!("{}={}", param.name, param.default))
+                .collect();
+            assert!(
+                Params::new(solution.params(), &defaults).is_ok(),
+                "day {}",
+                solution.day()
+            );
+        }
+    }
+
+    /// A reader failing on every read.
+    struct Broken;
+
+    impl Read for Broken {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("broken pipe"))
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = find(1)
+            .unwrap()
+            .parse(&mut "1721\n97x9\n".as_bytes())
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 1, line 2, column 1: invalid number: invalid digit found in string (`97x9`)"
+        );
+    }
+
+    proptest! {
+        /// The fuzz targets in `fuzz/` search much harder, this only keeps
+        /// the obvious panics out.
+        #[test]
+        fn parsers_do_not_panic(
+            day in 1..=25u8,
+            data in prop::collection::vec(
+                prop::sample::select(b"0123456789 -+:,()#.x\nLRFBNSEWabcemnsw\xff".to_vec()),
+                0..100,
+            ),
+        ) {
+            let solution = find(day).unwrap();
+            let parsed = panic::catch_unwind(AssertUnwindSafe(|| solution.parse(&mut data.as_slice()).is_ok()));
+            prop_assert!(parsed.is_ok(), "day {} panicked", day);
+        }
+    }
+}
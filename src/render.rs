@@ -0,0 +1,303 @@
+//! Pictures of the puzzles played out on a map, e.g. the generations of the
+//! automata of days 11, 17 and 24.
+//!
+//! A [`Frame`] is a grid of characters with background colours, which can be
+//! printed as plain text or with ANSI colours, or drawn as an [`Image`] and
+//! saved as PPM, PNG or an animated GIF.
+
+use std::{
+    any::Any,
+    collections::HashMap,
+    convert::TryFrom,
+    fmt,
+    io::{self, Write},
+    iter,
+    time::Duration,
+};
+
+use crate::{
+    days::{day11, day17, day20, day24, day3},
+    grid::Grid,
+    Params,
+};
+
+/// Colour of the cells without one in images.
+const BACKGROUND: Rgb = Rgb(0, 0, 0);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// Whether black text is easier to read on the colour than white text.
+    fn is_light(self) -> bool {
+        let Rgb(r, g, b) = self;
+        299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b) > 128_000
+    }
+}
+
+/// One character of a frame, with the colour of its background. Cells without
+/// a colour are left to the terminal's default.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cell {
+    pub glyph: char,
+    pub colour: Option<Rgb>,
+}
+
+impl Cell {
+    pub fn new(glyph: char, colour: Rgb) -> Self {
+        Cell {
+            glyph,
+            colour: Some(colour),
+        }
+    }
+
+    /// A character without a colour, e.g. of a caption.
+    pub fn text(glyph: char) -> Self {
+        Cell {
+            glyph,
+            colour: None,
+        }
+    }
+
+    pub fn blank() -> Self {
+        Cell::text(' ')
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.glyph)
+    }
+}
+
+pub type Frame = Grid<Cell>;
+
+/// Lays `panels` out left to right in rows of `columns`, each with its
+/// caption above it.
+pub fn mosaic(panels: &[(String, Frame)], columns: usize) -> Frame {
+    let columns = columns.max(1);
+    let cell_width = panels
+        .iter()
+        .map(|(caption, frame)| frame.width().max(caption.chars().count()))
+        .max()
+        .unwrap_or(0)
+        + 1;
+    let cell_height = panels
+        .iter()
+        .map(|(_, frame)| frame.height())
+        .max()
+        .unwrap_or(0)
+        + 2;
+    let rows = panels.len().div_ceil(columns);
+    let width = (cell_width * panels.len().min(columns)).saturating_sub(1);
+
+    let mut mosaic = Frame::new(width, (cell_height * rows).saturating_sub(1), Cell::blank());
+    for (i, (caption, frame)) in panels.iter().enumerate() {
+        let (left, top) = (i % columns * cell_width, i / columns * cell_height);
+        for (x, ch) in caption.chars().enumerate() {
+            mosaic[(left + x, top)] = Cell::text(ch);
+        }
+        for ((x, y), &cell) in frame.iter() {
+            mosaic[(left + x, top + 1 + y)] = cell;
+        }
+    }
+    mosaic
+}
+
+/// The frame with ANSI escape codes for 24-bit colours, one line per row.
+pub fn ansi(frame: &Frame) -> String {
+    let mut text = String::new();
+    for y in 0..frame.height() {
+        let mut current = None;
+        for cell in frame.row(y) {
+            if cell.colour != current {
+                match cell.colour {
+                    Some(colour @ Rgb(r, g, b)) => {
+                        let text_colour = if colour.is_light() { 30 } else { 97 };
+                        text += &format!("\x1b[{};48;2;{};{};{}m", text_colour, r, g, b);
+                    }
+                    None => text += "\x1b[0m",
+                }
+                current = cell.colour;
+            }
+            text.push(cell.glyph);
+        }
+        if current.is_some() {
+            text += "\x1b[0m";
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// A frame drawn with pixels, every cell as a square of them.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Image {
+    pixels: Grid<Rgb>,
+}
+
+impl Image {
+    /// Draws every cell of `frame` as a `scale` × `scale` square of its
+    /// colour. Glyphs are left out.
+    pub fn new(frame: &Frame, scale: usize) -> Self {
+        let scale = scale.max(1);
+        let pixels = Grid::from_fn(frame.width() * scale, frame.height() * scale, |(x, y)| {
+            frame[(x / scale, y / scale)].colour.unwrap_or(BACKGROUND)
+        });
+        Image { pixels }
+    }
+
+    pub fn width(&self) -> usize {
+        self.pixels.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.pixels.height()
+    }
+
+    /// Red, green and blue of every pixel, row by row.
+    fn rgb(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|(_, &Rgb(r, g, b))| vec![r, g, b])
+            .collect()
+    }
+
+    /// Writes the image as a binary PPM, see http://netpbm.sourceforge.net/doc/ppm.html.
+    pub fn write_ppm<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width(), self.height())?;
+        writer.write_all(&self.rgb())
+    }
+
+    pub fn write_png<W: Write>(&self, writer: W) -> io::Result<()> {
+        let (width, height) = (dimension(self.width())?, dimension(self.height())?);
+        let mut encoder = png::Encoder::new(writer, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.rgb())?;
+        Ok(writer.finish()?)
+    }
+}
+
+fn dimension<T: TryFrom<usize>>(size: usize) -> io::Result<T> {
+    T::try_from(size).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "image is too large"))
+}
+
+/// Writes `images` as an endlessly repeating animated GIF, `delay` apart.
+/// Smaller images are padded to the size of the largest one, and all of them
+/// together may have at most 256 colours.
+pub fn write_gif<W: Write>(images: &[Image], delay: Duration, writer: W) -> io::Result<()> {
+    let width = images.iter().map(Image::width).max().unwrap_or(1);
+    let height = images.iter().map(Image::height).max().unwrap_or(1);
+
+    let mut palette = HashMap::new();
+    let colours = images
+        .iter()
+        .flat_map(|image| image.pixels.iter().map(|(_, &colour)| colour))
+        .chain(iter::once(BACKGROUND));
+    for colour in colours {
+        let index = palette.len();
+        palette.entry(colour).or_insert(index);
+    }
+    if palette.len() > 256 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "more than 256 colours",
+        ));
+    }
+    let mut colours = vec![0; 3 * palette.len()];
+    for (&Rgb(r, g, b), &i) in &palette {
+        colours[3 * i..3 * i + 3].copy_from_slice(&[r, g, b]);
+    }
+
+    let (gif_width, gif_height) = (dimension(width)?, dimension(height)?);
+    let mut encoder =
+        gif::Encoder::new(writer, gif_width, gif_height, &colours).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    for image in images {
+        let mut buffer = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let colour = image.pixels.get((x, y)).copied().unwrap_or(BACKGROUND);
+                buffer.push(palette[&colour] as u8);
+            }
+        }
+        let frame = gif::Frame {
+            width: gif_width,
+            height: gif_height,
+            buffer: buffer.into(),
+            delay: dimension(delay.as_millis() as usize / 10)?,
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+/// Frames showing how the given part of a day is solved with `params`, `None`
+/// if the day has no animation. `input` is what [`DynSolution::parse`] returns
+/// for it.
+///
+/// [`DynSolution::parse`]: crate::registry::DynSolution::parse
+pub fn animation<'a>(
+    day: u8,
+    input: &'a dyn Any,
+    part: u8,
+    params: &Params,
+) -> Option<Box<dyn Iterator<Item = Frame> + 'a>> {
+    match day {
+        3 => input
+            .downcast_ref()
+            .map(|map| day3::animation(map, part, params)),
+        11 => input
+            .downcast_ref()
+            .map(|layout| day11::animation(layout, part, params)),
+        17 => input
+            .downcast_ref()
+            .map(|game| day17::animation(game, part, params)),
+        20 => input
+            .downcast_ref::<Vec<day20::Tile>>()
+            .map(|tiles| day20::animation(tiles, part, params)),
+        24 => input
+            .downcast_ref::<Vec<Vec<day24::Step>>>()
+            .map(|steps| day24::animation(steps, part, params)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let red = Rgb(200, 0, 0);
+        let frame = Frame::from_fn(2, 1, |(x, _)| match x {
+            0 => Cell::new('#', red),
+            _ => Cell::blank(),
+        });
+        assert_eq!(ansi(&frame), "\x1b[97;48;2;200;0;0m#\x1b[0m \n");
+
+        let panels = vec![("a".to_owned(), frame.clone()), ("bcd".to_owned(), frame)];
+        assert_eq!(mosaic(&panels, 1).to_string(), "a  \n#  \n   \nbcd\n#  ");
+        assert_eq!(mosaic(&panels, 2).to_string(), "a   bcd\n#   #  ");
+
+        let image = Image::new(&panels[0].1, 2);
+        assert_eq!((image.width(), image.height()), (4, 2));
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        let header = b"P6\n4 2\n255\n";
+        assert!(ppm.starts_with(header));
+        assert_eq!(ppm.len(), header.len() + 4 * 2 * 3);
+        assert_eq!(
+            ppm[header.len()..][..12],
+            [200, 0, 0, 200, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+        let mut gif = Vec::new();
+        write_gif(&[image.clone(), image], Duration::from_millis(50), &mut gif).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+    }
+}
@@ -0,0 +1,252 @@
+//! Creates and registers the files of a new day, used by `aoc new`.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Creates the module of `day`, its binary and its input directory under
+/// `root`, and registers the day in `src/days/mod.rs`. A fuzz target for its input
+/// parser is added as well if `fuzz/Cargo.toml` exists. Returns the created
+/// files.
+///
+/// Fails without touching anything if the day already exists. Existing
+/// inputs, examples and fuzz targets are kept.
+pub fn scaffold(root: &Path, day: u8, year: u16) -> io::Result<Vec<PathBuf>> {
+    let module = root.join(format!("src/days/day{}.rs", day));
+    let registry_path = root.join("src/days/mod.rs");
+    let registry = fs::read_to_string(&registry_path)?;
+    let registered = match register(&registry, day) {
+        Some(registered) if !module.exists() => registered,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("day {} already exists", day),
+            ))
+        }
+    };
+
+    let mut created = Vec::new();
+    create(&module, &template(day, year))?;
+    created.push(module);
+    fs::write(&registry_path, registered)?;
+
+    let binary = root.join(format!("src/bin/day{}.rs", day));
+    match create(&binary, &binary_source(day)) {
+        Ok(()) => created.push(binary),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e),
+    }
+
+    let inputs = root.join(format!("inputs/day{}", day));
+    fs::create_dir_all(&inputs)?;
+    let example = inputs.join("example.txt");
+    let sidecar = inputs.join("example.answers");
+    for (path, contents) in [(example, ""), (sidecar, "# part, answer\n1 -\n2 -\n")] {
+        match create(&path, contents) {
+            Ok(()) => created.push(path),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    let fuzz_manifest = root.join("fuzz/Cargo.toml");
+    let target = root.join(format!("fuzz/fuzz_targets/day{}.rs", day));
+    if fuzz_manifest.exists() {
+        match create(&target, &fuzz_target(day)) {
+            Ok(()) => {
+                OpenOptions::new()
+                    .append(true)
+                    .open(&fuzz_manifest)?
+                    .write_all(fuzz_bin(day).as_bytes())?;
+                created.push(target);
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(created)
+}
+
+/// Writes a file that must not exist yet.
+fn create(path: &Path, contents: &str) -> io::Result<()> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)?
+        .write_all(contents.as_bytes())
+}
+
+/// Source of a day's module, to be filled in.
+pub fn template(day: u8, year: u16) -> String {
+    format!(
+        r#"use std::io::BufRead;
+
+use crate::{{parse, Error, Solution}};
+
+/// https://adventofcode.com/{year}/day/{day}
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    const DAY: u8 = {day};
+
+    type Input = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, Error> {{
+        read_input(reader)
+    }}
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {{
+        part1(input)
+    }}
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {{
+        part2(input)
+    }}
+}}
+
+pub fn part1(_input: &[String]) -> i32 {{
+    unimplemented!()
+}}
+
+pub fn part2(_input: &[String]) -> i32 {{
+    unimplemented!()
+}}
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Vec<String>, Error> {{
+    parse::lines(reader)
+        .map(|line| line.map(|(_, line)| line))
+        .collect()
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use std::{{fs::File, io::BufReader}};
+
+    #[test]
+    fn test_solution() {{
+        let input = read_input(BufReader::new(File::open("inputs/day{day}/1.txt").unwrap())).unwrap();
+        assert_eq!(part1(&input), 0);
+        assert_eq!(part2(&input), 0);
+    }}
+}}
+"#,
+        day = day,
+        year = year
+    )
+}
+
+/// Source of the binary solving a new day from stdin.
+pub fn binary_source(day: u8) -> String {
+    format!(
+        r#"use advent_of_rust_2020::{{days::day{day}::Day{day}, solve_stdin}};
+
+fn main() {{
+    solve_stdin::<Day{day}>();
+}}
+"#,
+        day = day
+    )
+}
+
+/// Source of the fuzz target feeding arbitrary bytes to a new day's parser.
+pub fn fuzz_target(day: u8) -> String {
+    format!(
+        r#"#![no_main]
+
+use advent_of_rust_2020::days::day{day};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {{
+    let _ = day{day}::read_input(data);
+}});
+"#,
+        day = day
+    )
+}
+
+/// Entry of a fuzz target in `fuzz/Cargo.toml`.
+fn fuzz_bin(day: u8) -> String {
+    format!(
+        "\n[[bin]]\nname = \"day{0}\"\npath = \"fuzz_targets/day{0}.rs\"\ntest = false\ndoc = false\n",
+        day
+    )
+}
+
+/// Adds `day` to the module declarations and the `DAYS` registry of
+/// `src/days/mod.rs`, or returns `None` if it is registered already.
+pub fn register(registry: &str, day: u8) -> Option<String> {
+    let module = format!("pub mod day{};", day);
+    let entry = format!("    &day{0}::Day{0},", day);
+    let mut lines: Vec<String> = registry.lines().map(str::to_owned).collect();
+    if lines.iter().any(|line| *line == module || *line == entry) {
+        return None;
+    }
+
+    // Modules are kept in rustfmt's order, the registry in calendar order.
+    let module_name = |line: &str| {
+        line.strip_prefix("pub mod ")
+            .and_then(|rest| rest.strip_suffix(';'))
+            .map(str::to_owned)
+    };
+    let name = format!("day{}", day);
+    let position = lines
+        .iter()
+        .position(|line| module_name(line).is_some_and(|other| other > name))
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|line| module_name(line).is_some())
+                .map(|last| last + 1)
+        })
+        .unwrap_or(0);
+    lines.insert(position, module);
+
+    let registered_day = |line: &str| {
+        line.trim()
+            .strip_prefix("&day")
+            .and_then(|rest| rest.split("::").next())
+            .and_then(|number| number.parse::<u8>().ok())
+    };
+    let position = lines
+        .iter()
+        .position(|line| registered_day(line).is_some_and(|registered| registered > day))
+        .or_else(|| lines.iter().position(|line| line.starts_with("];")))?;
+    lines.insert(position, entry);
+
+    Some(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register() {
+        let registry = "pub mod day1;\n\
+                        pub mod day3;\n\
+                        \n\
+                        pub static DAYS: &[&dyn DynSolution] = &[\n    &day1::Day1,\n    &day3::Day3,\n];\n";
+        assert_eq!(
+            register(registry, 2).unwrap(),
+            "pub mod day1;\n\
+             pub mod day2;\n\
+             pub mod day3;\n\
+             \n\
+             pub static DAYS: &[&dyn DynSolution] = &[\n    &day1::Day1,\n    &day2::Day2,\n    &day3::Day3,\n];\n"
+        );
+        assert_eq!(
+            register(registry, 10).unwrap(),
+            "pub mod day1;\n\
+             pub mod day10;\n\
+             pub mod day3;\n\
+             \n\
+             pub static DAYS: &[&dyn DynSolution] = &[\n    &day1::Day1,\n    &day3::Day3,\n    &day10::Day10,\n];\n"
+        );
+        assert!(register(registry, 3).is_none());
+    }
+}
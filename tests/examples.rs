@@ -0,0 +1,39 @@
+//! Every `inputs/dayN/example*.txt` is solved and checked against the answers
+//! in its sidecar file, `build.rs` generates a test for each of them.
+
+use std::{fs, path::Path};
+
+use advent_of_rust_2020::{
+    answers::{self, Answers},
+    registry,
+};
+
+fn check(day: u8, path: &str) {
+    let solution = registry::find(day).unwrap();
+    let path = Path::new(path);
+    let data = fs::read(path).unwrap();
+    let hash = answers::hash_input(&data);
+
+    let mut expected = Answers::new(day);
+    assert!(
+        expected.load_sidecar(path, &hash).unwrap(),
+        "{} has no sidecar with the expected answers",
+        path.display()
+    );
+
+    let input = solution.parse(&mut data.as_slice()).unwrap();
+    for part in 1..=solution.parts() {
+        match expected.get(&hash, part) {
+            Some(answers::NOT_APPLICABLE) => {}
+            Some(answer) => assert_eq!(
+                solution.solve(input.as_ref(), part),
+                answer,
+                "part {}",
+                part
+            ),
+            None => panic!("{} has no answer for part {}", path.display(), part),
+        }
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));